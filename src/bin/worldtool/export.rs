@@ -0,0 +1,306 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crafty_mod::renderer::atlas;
+use crafty_mod::renderer::mesh::{ChunkMeshes, Mesh};
+use crafty_mod::world::chunk::{ChunkData, CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH};
+use crafty_mod::world::generation::VoidGenerator;
+use crafty_mod::world::{BlockPos, ChunkPos, World};
+
+//gltf constants
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const NEAREST: u32 = 9728;
+
+//the meshes of every render layer, with the same materials the renderer uses for them
+pub struct ExportMeshes {
+    layers: [(&'static str, Mesh); 3],
+}
+
+impl ExportMeshes {
+    //the faces of the blocks between the two corners, both included, meshed the same way as in
+    //the game as if everything outside of the box was air, so its sides are closed
+    pub fn build(world: &World, from: BlockPos, to: BlockPos) -> Self {
+        let world = &isolate(world, from, to);
+        let mut layers = [
+            ("opaque", Mesh::new()),
+            ("cutout", Mesh::new()),
+            ("translucent", Mesh::new()),
+        ];
+        let (from_chunk, to_chunk) = (from.chunk_pos(), to.chunk_pos());
+        for chunk_z in from_chunk.z..=to_chunk.z {
+            for chunk_x in from_chunk.x..=to_chunk.x {
+                let meshes = ChunkMeshes::build(world, ChunkPos::new(chunk_x, chunk_z));
+                let chunk_layers = [&meshes.opaque, &meshes.cutout, &meshes.translucent];
+                for ((_, mesh), chunk_mesh) in layers.iter_mut().zip(chunk_layers) {
+                    add_faces_inside(mesh, chunk_mesh, from, to);
+                }
+            }
+        }
+        Self { layers }
+    }
+
+    pub fn face_count(&self) -> usize {
+        self.layers
+            .iter()
+            .map(|(_, mesh)| mesh.indices.len() / 6)
+            .sum()
+    }
+}
+
+//a world with only the blocks inside of the box, surrounded by empty chunks
+fn isolate(world: &World, from: BlockPos, to: BlockPos) -> World {
+    let (from_chunk, to_chunk) = (from.chunk_pos(), to.chunk_pos());
+    let mut isolated = World::new(world.seed(), Box::new(VoidGenerator));
+    for chunk_z in from_chunk.z..=to_chunk.z {
+        for chunk_x in from_chunk.x..=to_chunk.x {
+            let pos = ChunkPos::new(chunk_x, chunk_z);
+            let mut chunk = ChunkData::new();
+            for y in from.y.max(0)..=to.y.min(CHUNK_HEIGHT as i32 - 1) {
+                for z in 0..CHUNK_DEPTH {
+                    for x in 0..CHUNK_WIDTH {
+                        let block_pos = BlockPos::new(
+                            pos.x * CHUNK_WIDTH as i32 + x as i32,
+                            y,
+                            pos.z * CHUNK_DEPTH as i32 + z as i32,
+                        );
+                        if (from.x..=to.x).contains(&block_pos.x)
+                            && (from.z..=to.z).contains(&block_pos.z)
+                        {
+                            chunk.set_block(x, y as usize, z, world.block(block_pos));
+                        }
+                    }
+                }
+            }
+            isolated.insert_chunk(pos, chunk);
+        }
+    }
+
+    //the empty chunks around the box are there so that its sides are lit the same way as in the
+    //game
+    for chunk_z in from_chunk.z - 1..=to_chunk.z + 1 {
+        for chunk_x in from_chunk.x - 1..=to_chunk.x + 1 {
+            isolated.generate_chunk(ChunkPos::new(chunk_x, chunk_z));
+        }
+    }
+    isolated
+}
+
+//copies the faces of `source` which belong to a block inside of the box
+fn add_faces_inside(mesh: &mut Mesh, source: &Mesh, from: BlockPos, to: BlockPos) {
+    for quad in source.indices.chunks(6) {
+        let first = quad[0] as usize;
+        let corners = &source.vertices[first..first + 4];
+
+        //the face's block is just behind the middle of the face
+        let (a, c, normal) = (
+            &corners[0].position,
+            &corners[2].position,
+            &corners[0].normal,
+        );
+        let inside = |a: f32, c: f32, normal: f32| ((a + c) * 0.5 - normal * 0.01).floor() as i32;
+        let pos = BlockPos::new(
+            inside(a.x, c.x, normal.x),
+            inside(a.y, c.y, normal.y),
+            inside(a.z, c.z, normal.z),
+        );
+        let contains =
+            |value: i32, from: i32, to: i32| value >= from.min(to) && value <= from.max(to);
+        if !(contains(pos.x, from.x, to.x)
+            && contains(pos.y, from.y, to.y)
+            && contains(pos.z, from.z, to.z))
+        {
+            continue;
+        }
+
+        let first_index = mesh.vertices.len() as i32;
+        mesh.vertices.extend_from_slice(corners);
+        for index in quad {
+            mesh.indices.push(first_index + (*index - first as i32));
+        }
+    }
+}
+
+//writes the obj and mtl files next to each other and the atlas as `<name>_atlas.png`
+pub fn write_obj(meshes: &ExportMeshes, path: &Path) -> io::Result<()> {
+    let name = path.file_stem().unwrap().to_string_lossy();
+    let mtl_name = format!("{}.mtl", name);
+    let atlas_name = format!("{}_atlas.png", name);
+    write_atlas(&path.with_file_name(&atlas_name))?;
+
+    let mut mtl = String::new();
+    let mut obj = format!("mtllib {}\n", mtl_name);
+    //obj indices start at 1 and count across all of the objects in the file
+    let mut offset = 1;
+    for (material, mesh) in meshes.layers.iter() {
+        if mesh.indices.is_empty() {
+            continue;
+        }
+        writeln!(mtl, "newmtl {}\nKd 1 1 1\nmap_Kd {}", material, atlas_name).unwrap();
+        if *material != "opaque" {
+            writeln!(mtl, "map_d {}", atlas_name).unwrap();
+        }
+        writeln!(mtl).unwrap();
+
+        writeln!(obj, "o {}\nusemtl {}", material, material).unwrap();
+        for vertex in mesh.vertices.iter() {
+            let position = &vertex.position;
+            writeln!(obj, "v {} {} {}", position.x, position.y, position.z).unwrap();
+        }
+        //the atlas' rows go down but obj texture coordinates go up
+        for vertex in mesh.vertices.iter() {
+            writeln!(obj, "vt {} {}", vertex.uv[0], 1.0 - vertex.uv[1]).unwrap();
+        }
+        for vertex in mesh.vertices.iter() {
+            let normal = &vertex.normal;
+            writeln!(obj, "vn {} {} {}", normal.x, normal.y, normal.z).unwrap();
+        }
+        for triangle in mesh.indices.chunks(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|index| index + offset);
+            writeln!(obj, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}").unwrap();
+        }
+        offset += mesh.vertices.len() as i32;
+    }
+
+    fs::write(path.with_file_name(mtl_name), mtl)?;
+    fs::write(path, obj)
+}
+
+//writes a gltf 2.0 file with a primitive for every layer, the vertex data goes to `<name>.bin`
+//and the atlas to `<name>_atlas.png`
+pub fn write_gltf(meshes: &ExportMeshes, path: &Path) -> io::Result<()> {
+    let name = path.file_stem().unwrap().to_string_lossy();
+    let bin_name = format!("{}.bin", name);
+    let atlas_name = format!("{}_atlas.png", name);
+    write_atlas(&path.with_file_name(&atlas_name))?;
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut primitives = Vec::new();
+    let mut materials = Vec::new();
+
+    //adds the data as a buffer view with an accessor reading it, returning the accessor's index
+    let mut add_accessor =
+        |data: Vec<u8>, target: u32, component_type: u32, kind: &str, count: usize, extra: &str| {
+            views.push(format!(
+                r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+                buffer.len(),
+                data.len(),
+                target
+            ));
+            buffer.extend(data);
+            accessors.push(format!(
+                r#"{{"bufferView":{},"componentType":{},"count":{},"type":"{}"{}}}"#,
+                views.len() - 1,
+                component_type,
+                count,
+                kind,
+                extra
+            ));
+            accessors.len() - 1
+        };
+
+    for (material, mesh) in meshes.layers.iter() {
+        if mesh.indices.is_empty() {
+            continue;
+        }
+        let count = mesh.vertices.len();
+
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        for vertex in mesh.vertices.iter() {
+            let position = [vertex.position.x, vertex.position.y, vertex.position.z];
+            for axis in 0..3 {
+                min[axis] = min[axis].min(position[axis]);
+                max[axis] = max[axis].max(position[axis]);
+            }
+            positions.extend(position.iter().flat_map(|value| value.to_le_bytes()));
+            let normal = [vertex.normal.x, vertex.normal.y, vertex.normal.z];
+            normals.extend(normal.iter().flat_map(|value| value.to_le_bytes()));
+            uvs.extend(vertex.uv.iter().flat_map(|value| value.to_le_bytes()));
+        }
+        let indices: Vec<u8> = mesh
+            .indices
+            .iter()
+            .flat_map(|index| (*index as u32).to_le_bytes())
+            .collect();
+
+        //positions are the only attribute which needs its bounds
+        let bounds = format!(
+            r#","min":[{},{},{}],"max":[{},{},{}]"#,
+            min[0], min[1], min[2], max[0], max[1], max[2]
+        );
+        let position = add_accessor(positions, ARRAY_BUFFER, FLOAT, "VEC3", count, &bounds);
+        let normal = add_accessor(normals, ARRAY_BUFFER, FLOAT, "VEC3", count, "");
+        let uv = add_accessor(uvs, ARRAY_BUFFER, FLOAT, "VEC2", count, "");
+        let index = add_accessor(
+            indices,
+            ELEMENT_ARRAY_BUFFER,
+            UNSIGNED_INT,
+            "SCALAR",
+            mesh.indices.len(),
+            "",
+        );
+
+        let alpha = match *material {
+            "opaque" => r#""alphaMode":"OPAQUE""#,
+            "cutout" => r#""alphaMode":"MASK","alphaCutoff":0.5,"doubleSided":true"#,
+            _ => r#""alphaMode":"BLEND","doubleSided":true"#,
+        };
+        materials.push(format!(
+            r#"{{"name":"{}","pbrMetallicRoughness":{{"baseColorTexture":{{"index":0}},"metallicFactor":0,"roughnessFactor":1}},{}}}"#,
+            material, alpha
+        ));
+        primitives.push(format!(
+            r#"{{"attributes":{{"POSITION":{},"NORMAL":{},"TEXCOORD_0":{}}},"indices":{},"material":{}}}"#,
+            position,
+            normal,
+            uv,
+            index,
+            materials.len() - 1
+        ));
+    }
+
+    let gltf = format!(
+        r#"{{
+"asset":{{"version":"2.0","generator":"worldtool"}},
+"scene":0,
+"scenes":[{{"nodes":[0]}}],
+"nodes":[{{"mesh":0,"name":"{}"}}],
+"meshes":[{{"primitives":[{}]}}],
+"materials":[{}],
+"textures":[{{"sampler":0,"source":0}}],
+"samplers":[{{"magFilter":{},"minFilter":{}}}],
+"images":[{{"uri":"{}"}}],
+"buffers":[{{"uri":"{}","byteLength":{}}}],
+"bufferViews":[{}],
+"accessors":[{}]
+}}
+"#,
+        name,
+        primitives.join(","),
+        materials.join(","),
+        NEAREST,
+        NEAREST,
+        atlas_name,
+        bin_name,
+        buffer.len(),
+        views.join(","),
+        accessors.join(",")
+    );
+
+    fs::write(path.with_file_name(bin_name), &buffer)?;
+    fs::write(path, gltf)
+}
+
+fn write_atlas(path: &Path) -> io::Result<()> {
+    atlas::build_atlas().save(path).map_err(io::Error::other)
+}
@@ -0,0 +1,385 @@
+//command line tool for working with worlds without opening a window
+mod export;
+mod map;
+
+use std::collections::HashMap;
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::time::Instant;
+
+use image::ImageError;
+use thiserror::Error;
+
+use crafty_mod::config::{Config, ConfigError};
+use crafty_mod::world::chunk::{CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH};
+use crafty_mod::world::generation::GeneratorError;
+use crafty_mod::world::ore;
+use crafty_mod::world::save::{self, SaveError};
+use crafty_mod::world::schematic::{Schematic, SchematicError};
+use crafty_mod::world::{BlockPos, ChunkPos, World};
+
+use crate::export::ExportMeshes;
+
+const USAGE: &str = "usage: worldtool <command> [--option value]...
+
+commands:
+  export      export the blocks between `--from` and `--to` of a saved world given by `--world`,
+              or of a freshly generated region, to the .obj, .gltf or .schem file given by `--out`
+  generate    generate a region of chunks and save it to the folder given by `--out`
+  import      paste the .schem file given by `--in` into the saved world given by `--world` with
+              its lowest corner at `--at`
+  map         draw a map of a saved world given by `--world`, or of a freshly generated
+              region, to the png given by `--out`
+  ores        count the ores in a region of generated chunks for every height band
+
+options:
+  --seed <seed>             these five default to the values in config.txt
+  --generator <generator>
+  --caves <true|false>
+  --layers <layers>
+  --ores <veins>
+  --radius <chunks>         size of the region around chunk (0, 0), defaults to 4
+  --out <path>              where `generate` saves the world and `map` and `export` their files
+  --world <folder>          saved world to use with `map`, `export` and `import`
+  --from <x,y,z>            corners of the box of blocks to `export`, both included
+  --to <x,y,z>
+  --in <path>               schematic to `import`
+  --at <x,y,z>              where `import` puts the schematic's lowest corner
+  --band <blocks>           height of a band for `ores`, defaults to 5";
+
+#[derive(Debug, Error)]
+enum ToolError {
+    #[error{"{0}"}]
+    ConfigError(#[from] ConfigError),
+    #[error{"{0}"}]
+    GeneratorError(#[from] GeneratorError),
+    #[error{"{0}"}]
+    SaveError(#[from] SaveError),
+    #[error{"{0}"}]
+    SchematicError(#[from] SchematicError),
+    #[error{"{0}"}]
+    ImageError(#[from] ImageError),
+    #[error{"{0}"}]
+    IoError(#[from] io::Error),
+    #[error("Missing command")]
+    MissingCommand,
+    #[error("Unknown command `{0}`")]
+    UnknownCommand(String),
+    #[error("Unknown option `{0}`")]
+    UnknownOption(String),
+    #[error("Missing option `{0}`")]
+    MissingOption(String),
+    #[error("Unsupported export format `{0}`, use .obj, .gltf or .schem")]
+    UnknownFormat(String),
+    #[error("There's nothing to export between the corners")]
+    NothingToExport,
+    #[error("Missing value for `{0}`")]
+    MissingValue(String),
+    #[error("Invalid value `{1}` for `{0}`")]
+    InvalidValue(String, String),
+}
+
+//`--key value` pairs following the command
+struct Options {
+    values: HashMap<String, String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, ToolError> {
+        let mut values = HashMap::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let key = arg
+                .strip_prefix("--")
+                .ok_or_else(|| ToolError::UnknownOption(arg.clone()))?;
+            let value = args
+                .next()
+                .ok_or_else(|| ToolError::MissingValue(key.to_string()))?;
+            values.insert(key.to_string(), value.clone());
+        }
+        Ok(Self { values })
+    }
+
+    fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, ToolError> {
+        match self.values.get(key) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| ToolError::InvalidValue(key.to_string(), value.clone())),
+            None => Ok(default),
+        }
+    }
+
+    fn get_pos(&self, key: &str) -> Result<BlockPos, ToolError> {
+        let value = self.require(key)?;
+        let invalid = || ToolError::InvalidValue(key.to_string(), value.clone());
+        let coordinates = value
+            .split(',')
+            .map(|coordinate| coordinate.trim().parse().map_err(|_| invalid()))
+            .collect::<Result<Vec<i32>, ToolError>>()?;
+        match coordinates[..] {
+            [x, y, z] => Ok(BlockPos::new(x, y, z)),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn require(&self, key: &str) -> Result<&String, ToolError> {
+        self.values
+            .get(key)
+            .ok_or_else(|| ToolError::MissingOption(key.to_string()))
+    }
+}
+
+//the chunks of a saved world, the ones which aren't in the save are generated like the saved
+//world would
+fn load_saved(dir: &Path, chunks: impl Iterator<Item = ChunkPos>) -> Result<World, ToolError> {
+    let mut world = World::load(dir)?;
+    for pos in chunks {
+        world.generate_chunk(pos);
+    }
+    Ok(world)
+}
+
+//the chunks in a square around the origin
+fn generate_region(config: &Config, options: &Options) -> Result<(World, i32), ToolError> {
+    let radius: i32 = options.get("radius", 4)?;
+    if radius < 0 {
+        return Err(ToolError::InvalidValue(
+            "radius".to_string(),
+            radius.to_string(),
+        ));
+    }
+
+    let mut world = World::new(config.seed, config.world_generator()?);
+    for z in -radius..=radius {
+        for x in -radius..=radius {
+            world.generate_chunk(ChunkPos::new(x, z));
+        }
+    }
+    Ok((world, radius))
+}
+
+fn generate(config: &Config, options: &Options) -> Result<(), ToolError> {
+    let out = PathBuf::from(options.require("out")?);
+
+    let start = Instant::now();
+    let (world, radius) = generate_region(config, options)?;
+    let generated = start.elapsed();
+    world.save(&out, &config.generator_settings())?;
+    let saved = start.elapsed() - generated;
+
+    let chunks = (radius * 2 + 1) * (radius * 2 + 1);
+    println!(
+        "generated {} chunks in {:.2?} ({:.2?} per chunk), saved to `{}` in {:.2?}",
+        chunks,
+        generated,
+        generated / chunks as u32,
+        out.display(),
+        saved
+    );
+    Ok(())
+}
+
+fn draw_map(config: &Config, options: &Options) -> Result<(), ToolError> {
+    let out = PathBuf::from(options.require("out")?);
+
+    let image = match options.values.get("world") {
+        Some(dir) => {
+            let dir = Path::new(dir);
+            let mut chunks = HashMap::new();
+            for pos in save::saved_chunks(dir)? {
+                chunks.insert(pos, save::read_chunk(dir, pos)?);
+            }
+            map::render(&chunks.iter().map(|(pos, chunk)| (*pos, chunk)).collect())
+        }
+        None => {
+            let (world, _) = generate_region(config, options)?;
+            map::render(
+                &world
+                    .loaded_chunks()
+                    .map(|pos| (pos, world.chunk(pos).unwrap()))
+                    .collect(),
+            )
+        }
+    };
+    image.save(&out)?;
+
+    println!(
+        "saved a {}x{} map to `{}`",
+        image.width(),
+        image.height(),
+        out.display()
+    );
+    Ok(())
+}
+
+fn export(config: &Config, options: &Options) -> Result<(), ToolError> {
+    let out = PathBuf::from(options.require("out")?);
+    let (from, to) = (options.get_pos("from")?, options.get_pos("to")?);
+    let (from, to) = (
+        BlockPos::new(from.x.min(to.x), from.y.min(to.y), from.z.min(to.z)),
+        BlockPos::new(from.x.max(to.x), from.y.max(to.y), from.z.max(to.z)),
+    );
+
+    let (from_chunk, to_chunk) = (from.chunk_pos(), to.chunk_pos());
+    let chunks = (from_chunk.z..=to_chunk.z)
+        .flat_map(|z| (from_chunk.x..=to_chunk.x).map(move |x| ChunkPos::new(x, z)));
+    let world = match options.values.get("world") {
+        Some(dir) => load_saved(Path::new(dir), chunks)?,
+        None => {
+            let mut world = World::new(config.seed, config.world_generator()?);
+            for pos in chunks {
+                world.generate_chunk(pos);
+            }
+            world
+        }
+    };
+
+    let extension = out.extension().and_then(|extension| extension.to_str());
+    if extension == Some("schem") {
+        let schematic = Schematic::copy(&world, from, to);
+        schematic.write(&out)?;
+        let (width, height, length) = schematic.size();
+        println!(
+            "exported a {}x{}x{} schematic to `{}`",
+            width,
+            height,
+            length,
+            out.display()
+        );
+        return Ok(());
+    }
+
+    let meshes = ExportMeshes::build(&world, from, to);
+    if meshes.face_count() == 0 {
+        return Err(ToolError::NothingToExport);
+    }
+    match extension {
+        Some("obj") => export::write_obj(&meshes, &out)?,
+        Some("gltf") => export::write_gltf(&meshes, &out)?,
+        _ => return Err(ToolError::UnknownFormat(out.display().to_string())),
+    }
+
+    println!(
+        "exported {} faces to `{}`",
+        meshes.face_count(),
+        out.display()
+    );
+    Ok(())
+}
+
+fn import(options: &Options) -> Result<(), ToolError> {
+    let dir = Path::new(options.require("world")?);
+    let (schematic, unknown) = Schematic::read(Path::new(options.require("in")?))?;
+    let at = options.get_pos("at")?;
+    let (width, height, length) = schematic.size();
+
+    //only the chunks the schematic ends up in are loaded and written back
+    let to = BlockPos::new(
+        at.x + width as i32 - 1,
+        at.y + height as i32 - 1,
+        at.z + length as i32 - 1,
+    );
+    let (from_chunk, to_chunk) = (at.chunk_pos(), to.chunk_pos());
+    let chunks = (from_chunk.z..=to_chunk.z)
+        .flat_map(|z| (from_chunk.x..=to_chunk.x).map(move |x| ChunkPos::new(x, z)));
+    let level = save::read_level(dir)?;
+    let mut world = load_saved(dir, chunks)?;
+
+    let placed = schematic.paste(&mut world, at);
+    world.save(dir, &level.generator)?;
+
+    println!(
+        "placed {} of the {} blocks of the {}x{}x{} schematic",
+        placed,
+        width * height * length,
+        width,
+        height,
+        length
+    );
+    if !unknown.is_empty() {
+        println!("unknown blocks replaced with air: {}", unknown.join(", "));
+    }
+    Ok(())
+}
+
+fn count_ores(config: &Config, options: &Options) -> Result<(), ToolError> {
+    let band: usize = options.get("band", 5)?;
+    if band == 0 {
+        return Err(ToolError::InvalidValue("band".to_string(), "0".to_string()));
+    }
+    let veins = ore::parse_veins(&config.ores)?;
+    let (world, radius) = generate_region(config, options)?;
+
+    let bands = CHUNK_HEIGHT.div_ceil(band);
+    let mut counts = vec![vec![0; veins.len()]; bands];
+    for chunk_z in -radius..=radius {
+        for chunk_x in -radius..=radius {
+            let chunk = ChunkPos::new(chunk_x, chunk_z);
+            for y in 0..CHUNK_HEIGHT {
+                for z in 0..CHUNK_DEPTH {
+                    for x in 0..CHUNK_WIDTH {
+                        let block = world.block(chunk.block_pos(x, y, z));
+                        if let Some(ore) = veins.iter().position(|vein| vein.ore == block) {
+                            counts[y / band][ore] += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let chunks = (radius * 2 + 1) * (radius * 2 + 1);
+    println!("ores in {} chunks", chunks);
+    print!("{:>8}", "height");
+    for vein in &veins {
+        print!("{:>12}", vein.ore.name());
+    }
+    println!();
+    for (index, band_counts) in counts.iter().enumerate().rev() {
+        let bottom = index * band;
+        let top = (bottom + band).min(CHUNK_HEIGHT) - 1;
+        print!("{:>8}", format!("{}-{}", bottom, top));
+        for count in band_counts {
+            print!("{:>12}", count);
+        }
+        println!();
+    }
+    print!("{:>8}", "total");
+    for ore in 0..veins.len() {
+        print!("{:>12}", counts.iter().map(|band| band[ore]).sum::<u32>());
+    }
+    println!();
+
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), ToolError> {
+    let (command, args) = args.split_first().ok_or(ToolError::MissingCommand)?;
+    let options = Options::parse(args)?;
+    let mut config = Config::load(Path::new("config.txt"))?;
+    config.seed = options.get("seed", config.seed)?;
+    config.generator = options.get("generator", config.generator)?;
+    config.caves = options.get("caves", config.caves)?;
+    config.superflat_layers = options.get("layers", config.superflat_layers)?;
+    config.ores = options.get("ores", config.ores)?;
+
+    match command.as_str() {
+        "export" => export(&config, &options),
+        "generate" => generate(&config, &options),
+        "import" => import(&options),
+        "map" => draw_map(&config, &options),
+        "ores" => count_ores(&config, &options),
+        _ => Err(ToolError::UnknownCommand(command.clone())),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("{}\n\n{}", err, USAGE);
+        process::exit(1);
+    }
+}
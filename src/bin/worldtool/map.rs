@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use image::{Rgba, RgbaImage};
+
+use crafty_mod::world::chunk::{ChunkData, CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH};
+use crafty_mod::world::{Block, ChunkPos};
+
+//how much brighter or darker a column gets for every block it's higher or lower than the one to
+//the north of it, the same kind of shading as on paper maps lit from the north west
+const SLOPE_SHADING: f32 = 0.08;
+//how much darker the water gets for every block it's deep
+const WATER_DEPTH_SHADING: f32 = 0.12;
+
+//topmost block of the column which isn't air and how high it is
+fn top_block(chunk: &ChunkData, x: usize, z: usize) -> Option<(usize, Block)> {
+    (0..CHUNK_HEIGHT)
+        .rev()
+        .map(|y| (y, chunk.block(x, y, z)))
+        .find(|(_, block)| *block != Block::Air)
+}
+
+fn rgb(colour: [u8; 4]) -> [f32; 3] {
+    [colour[0] as f32, colour[1] as f32, colour[2] as f32]
+}
+
+fn column_colour(chunk: &ChunkData, x: usize, z: usize) -> Option<([f32; 3], usize)> {
+    let (height, block) = top_block(chunk, x, z)?;
+    let mut colour = rgb(block.colour());
+
+    //the ground shows through shallow fluids
+    if block.fluid().is_some() {
+        let floor = (0..height)
+            .rev()
+            .map(|y| (y, chunk.block(x, y, z)))
+            .find(|(_, below)| !below.is_same_kind(&block));
+        if let Some((floor_height, floor)) = floor {
+            let depth = (height - floor_height) as f32;
+            let visible = (1.0 - depth * WATER_DEPTH_SHADING).max(0.0) * 0.5;
+            let floor = rgb(floor.colour());
+            for channel in 0..3 {
+                colour[channel] = colour[channel] * (1.0 - visible) + floor[channel] * visible;
+            }
+        }
+    }
+
+    Some((colour, height))
+}
+
+//a pixel for every column of the chunks with the colour of its top block, columns of missing
+//chunks stay transparent
+pub fn render(chunks: &HashMap<ChunkPos, &ChunkData>) -> RgbaImage {
+    let min_x = chunks.keys().map(|pos| pos.x).min().unwrap_or(0);
+    let max_x = chunks.keys().map(|pos| pos.x).max().unwrap_or(-1);
+    let min_z = chunks.keys().map(|pos| pos.z).min().unwrap_or(0);
+    let max_z = chunks.keys().map(|pos| pos.z).max().unwrap_or(-1);
+    let width = (max_x - min_x + 1) as u32 * CHUNK_WIDTH as u32;
+    let depth = (max_z - min_z + 1) as u32 * CHUNK_DEPTH as u32;
+
+    let mut heights: HashMap<(u32, u32), usize> = HashMap::new();
+    let mut image = RgbaImage::new(width, depth);
+    for (pos, chunk) in chunks {
+        for z in 0..CHUNK_DEPTH {
+            for x in 0..CHUNK_WIDTH {
+                let pixel_x = (pos.x - min_x) as u32 * CHUNK_WIDTH as u32 + x as u32;
+                let pixel_z = (pos.z - min_z) as u32 * CHUNK_DEPTH as u32 + z as u32;
+                if let Some((colour, height)) = column_colour(chunk, x, z) {
+                    let [r, g, b] = colour.map(|channel| channel as u8);
+                    image.put_pixel(pixel_x, pixel_z, Rgba([r, g, b, 255]));
+                    heights.insert((pixel_x, pixel_z), height);
+                }
+            }
+        }
+    }
+
+    //shading needs the heights of the neighbouring chunks, so it's done once everything is drawn
+    for ((pixel_x, pixel_z), height) in &heights {
+        let north = match pixel_z.checked_sub(1) {
+            Some(north_z) => heights.get(&(*pixel_x, north_z)),
+            None => None,
+        };
+        let slope = north.map_or(0.0, |north| *height as f32 - *north as f32);
+        let amount = (1.0 + slope * SLOPE_SHADING).clamp(0.6, 1.4);
+
+        let pixel = image.get_pixel_mut(*pixel_x, *pixel_z);
+        for channel in 0..3 {
+            pixel[channel] = (pixel[channel] as f32 * amount).clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    image
+}
@@ -0,0 +1,132 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::world::generation::{GeneratorError, GeneratorKind, GeneratorSettings, WorldGenerator};
+use crate::world::ore::{self, ORES};
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error{"{0}"}]
+    IoError(#[from] io::Error),
+    #[error("Line {0} is not in the `key = value` format")]
+    InvalidLine(usize),
+    #[error("Invalid value `{1}` for `{0}`")]
+    InvalidValue(String, String),
+    #[error("Unknown config key `{0}`")]
+    UnknownKey(String),
+}
+
+//settings read from a plain text file with one `key = value` pair per line, `#` starts a comment
+pub struct Config {
+    pub seed: i64,
+    pub generator: GeneratorKind,
+    //only used by the noise generator
+    pub caves: bool,
+    //only used by the superflat generator, see `FlatGenerator::new`
+    pub superflat_layers: String,
+    //only used by the noise generator, see `ore::parse_veins`
+    pub ores: String,
+    //world time in ticks the game starts at
+    pub start_time: u64,
+    pub freeze_time: bool,
+    //folder of a saved world to play in instead of generating a new one, empty for a new world
+    pub world: String,
+    //in chunks, also decides where the fog ends
+    pub render_distance: i32,
+    pub fog: FogMode,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FogMode {
+    Linear,
+    Exponential,
+}
+
+impl FromStr for FogMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "linear" => Ok(FogMode::Linear),
+            "exponential" => Ok(FogMode::Exponential),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            seed: 883_279_212_983_182_319,
+            generator: GeneratorKind::Noise,
+            caves: true,
+            superflat_layers: "1*stone,3*dirt,1*grass".to_string(),
+            ores: ore::format_veins(&ORES),
+            start_time: 1000,
+            freeze_time: false,
+            world: String::new(),
+            render_distance: 4,
+            fog: FogMode::Linear,
+        }
+    }
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> Result<T, ConfigError> {
+    value
+        .parse()
+        .map_err(|_| ConfigError::InvalidValue(key.to_string(), value.to_string()))
+}
+
+impl Config {
+    //a missing file isn't an error, the defaults are used instead
+    pub fn load(filepath: &Path) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        if !filepath.exists() {
+            return Ok(config);
+        }
+
+        let src = fs::read_to_string(filepath)?;
+        for (line_number, line) in src.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(ConfigError::InvalidLine(line_number + 1))?;
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                "seed" => config.seed = parse(key, value)?,
+                "generator" => config.generator = parse(key, value)?,
+                "caves" => config.caves = parse(key, value)?,
+                "superflat_layers" => config.superflat_layers = value.to_string(),
+                "ores" => config.ores = value.to_string(),
+                "start_time" => config.start_time = parse(key, value)?,
+                "freeze_time" => config.freeze_time = parse(key, value)?,
+                "world" => config.world = value.to_string(),
+                "render_distance" => config.render_distance = parse(key, value)?,
+                "fog" => config.fog = parse(key, value)?,
+                _ => return Err(ConfigError::UnknownKey(key.to_string())),
+            }
+        }
+
+        Ok(config)
+    }
+
+    pub fn generator_settings(&self) -> GeneratorSettings {
+        GeneratorSettings {
+            kind: self.generator,
+            caves: self.caves,
+            superflat_layers: self.superflat_layers.clone(),
+            ores: self.ores.clone(),
+        }
+    }
+
+    pub fn world_generator(&self) -> Result<Box<dyn WorldGenerator>, GeneratorError> {
+        self.generator_settings().build(self.seed)
+    }
+}
@@ -0,0 +1,283 @@
+use crate::console::{CommandContext, CommandError, Console};
+use crate::renderer::debug::{DebugSettings, DebugView};
+use crate::utils::math;
+use crate::world::block::Block;
+use crate::world::chunk::CHUNK_HEIGHT;
+use crate::world::crafting::CraftingGrid;
+use crate::world::player::GameMode;
+use crate::world::position::BlockPos;
+
+pub fn register(console: &mut Console) {
+    console.register(
+        "debug",
+        "<wireframe|borders|normal|normals|light|overdraw|off>",
+        debug,
+    );
+    console.register("craft", "<block> [times]", craft);
+    console.register("fill", "<x1> <y1> <z1> <x2> <y2> <z2> <block>", fill);
+    console.register("gamemode", "<creative|survival>", gamemode);
+    console.register("give", "<block> [count]", give);
+    console.register("redo", "", redo);
+    console.register("seed", "", seed);
+    console.register("time", "set <ticks|day|noon|night|midnight>", time);
+    console.register("tp", "<x> <y> <z>", tp);
+    console.register("undo", "", undo);
+}
+
+//how far from the origin coordinates can be along each axis. below 2^20 an f32 still steps by
+//1/16 of a block or less, further out movement and block picking get visibly coarse
+const MAX_COORDINATE: f32 = 1_000_000.0;
+
+//a coordinate, either absolute or relative to `current` when it starts with `~`
+fn coordinate(arg: &str, current: f32) -> Result<f32, CommandError> {
+    let invalid = || CommandError::InvalidNumber(arg.to_string());
+    let number = |text: &str| match text.parse::<f32>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(invalid()),
+    };
+    let value = match arg.strip_prefix('~') {
+        Some("") => current,
+        Some(offset) => current + number(offset)?,
+        None => number(arg)?,
+    };
+    if value.abs() > MAX_COORDINATE {
+        return Err(CommandError::OutsideOfWorld(arg.to_string()));
+    }
+    Ok(value)
+}
+
+fn position(args: &[&str], current: &math::Vec3) -> Result<math::Vec3, CommandError> {
+    Ok(math::Vec3::new(
+        coordinate(args[0], current.x)?,
+        coordinate(args[1], current.y)?,
+        coordinate(args[2], current.z)?,
+    ))
+}
+
+fn block_position(args: &[&str], current: &math::Vec3) -> Result<BlockPos, CommandError> {
+    let position = position(args, current)?;
+    Ok(BlockPos::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    ))
+}
+
+fn block(name: &str) -> Result<Block, CommandError> {
+    Block::from_name(name).ok_or_else(|| CommandError::UnknownBlock(name.to_string()))
+}
+
+fn tp(context: &mut CommandContext, args: &[&str]) -> Result<String, CommandError> {
+    if args.len() != 3 {
+        return Err(CommandError::Usage("tp <x> <y> <z>"));
+    }
+    let position = position(args, &context.player.position)?;
+    //the world only goes up to the top of the chunks
+    if !(0.0..=CHUNK_HEIGHT as f32).contains(&position.y) {
+        return Err(CommandError::OutsideOfWorld(args[1].to_string()));
+    }
+    let output = format!(
+        "Teleported to {:.1} {:.1} {:.1}",
+        position.x, position.y, position.z
+    );
+    context.player.position = position;
+    Ok(output)
+}
+
+fn time(context: &mut CommandContext, args: &[&str]) -> Result<String, CommandError> {
+    let usage = CommandError::Usage("time set <ticks|day|noon|night|midnight>");
+    let ticks = match args {
+        ["set", "day"] => 1000,
+        ["set", "noon"] => 6000,
+        ["set", "night"] => 13000,
+        ["set", "midnight"] => 18000,
+        ["set", ticks] => ticks
+            .parse()
+            .map_err(|_| CommandError::InvalidNumber(ticks.to_string()))?,
+        _ => return Err(usage),
+    };
+    context.world.time_mut().set(ticks);
+    Ok(format!("Set the time to {}", ticks))
+}
+
+fn give(context: &mut CommandContext, args: &[&str]) -> Result<String, CommandError> {
+    let (name, count) = match args {
+        [name] => (name, 1),
+        [name, count] => (
+            name,
+            count
+                .parse()
+                .map_err(|_| CommandError::InvalidNumber(count.to_string()))?,
+        ),
+        _ => return Err(CommandError::Usage("give <block> [count]")),
+    };
+    if count == 0 {
+        return Err(CommandError::InvalidCount(count.to_string()));
+    }
+    let item = block(name)?;
+    if !item.is_item() {
+        return Err(CommandError::NotAnItem(name.to_string()));
+    }
+    let left = context.player.inventory.add(item, count);
+    if left > 0 {
+        Ok(format!(
+            "Gave {} {}, {} didn't fit",
+            count - left,
+            name,
+            left
+        ))
+    } else {
+        Ok(format!("Gave {} {}", count, name))
+    }
+}
+
+fn gamemode(context: &mut CommandContext, args: &[&str]) -> Result<String, CommandError> {
+    let [name] = args else {
+        return Err(CommandError::Usage("gamemode <creative|survival>"));
+    };
+    let game_mode: GameMode = name
+        .parse()
+        .map_err(|_| CommandError::UnknownGameMode(name.to_string()))?;
+    context.player.game_mode = game_mode;
+    Ok(format!("Game mode set to {}", game_mode.name()))
+}
+
+fn seed(context: &mut CommandContext, _args: &[&str]) -> Result<String, CommandError> {
+    Ok(format!("Seed: {}", context.world.seed()))
+}
+
+//goes through the editor so it can be undone like any other edit
+fn fill(context: &mut CommandContext, args: &[&str]) -> Result<String, CommandError> {
+    if args.len() != 7 {
+        return Err(CommandError::Usage(
+            "fill <x1> <y1> <z1> <x2> <y2> <z2> <block>",
+        ));
+    }
+    let from = block_position(&args[0..3], &context.player.position)?;
+    let to = block_position(&args[3..6], &context.player.position)?;
+    let block = block(args[6])?;
+
+    context.editor.set_first_corner(from);
+    context.editor.set_second_corner(to);
+    let changed = context.editor.fill(context.world, block)?;
+    Ok(format!("Changed {} blocks", changed))
+}
+
+fn undo(context: &mut CommandContext, _args: &[&str]) -> Result<String, CommandError> {
+    if context.editor.undo(context.world) {
+        Ok("Undid the last edit".to_string())
+    } else {
+        Ok("Nothing to undo".to_string())
+    }
+}
+
+fn redo(context: &mut CommandContext, _args: &[&str]) -> Result<String, CommandError> {
+    if context.editor.redo(context.world) {
+        Ok("Redid the last undone edit".to_string())
+    } else {
+        Ok("Nothing to redo".to_string())
+    }
+}
+
+//wireframe and chunk borders are switched on and off, the views replace each other
+fn debug(context: &mut CommandContext, args: &[&str]) -> Result<String, CommandError> {
+    let usage = CommandError::Usage("debug <wireframe|borders|normal|normals|light|overdraw|off>");
+    let [mode] = args else {
+        return Err(usage);
+    };
+    let debug = &mut *context.debug;
+    let on_off = |on: bool| if on { "on" } else { "off" };
+    match *mode {
+        "wireframe" => {
+            debug.wireframe = !debug.wireframe;
+            Ok(format!("Wireframe {}", on_off(debug.wireframe)))
+        }
+        "borders" => {
+            debug.chunk_borders = !debug.chunk_borders;
+            Ok(format!("Chunk borders {}", on_off(debug.chunk_borders)))
+        }
+        "off" => {
+            *debug = DebugSettings::new();
+            Ok("Debug rendering off".to_string())
+        }
+        view => {
+            debug.view = view.parse::<DebugView>().map_err(|_| usage)?;
+            Ok(format!("Showing the {} view", debug.view.name()))
+        }
+    }
+}
+
+//how far away from the player a crafting table can be to craft with it
+const CRAFTING_TABLE_REACH: i32 = 4;
+
+fn near_crafting_table(context: &CommandContext) -> bool {
+    let position = &context.player.position;
+    let centre = BlockPos::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    );
+    let reach = -CRAFTING_TABLE_REACH..=CRAFTING_TABLE_REACH;
+    reach.clone().any(|dx| {
+        reach.clone().any(|dy| {
+            reach.clone().any(|dz| {
+                let pos = BlockPos::new(centre.x + dx, centre.y + dy, centre.z + dz);
+                context.world.block(pos) == Block::CraftingTable
+            })
+        })
+    })
+}
+
+//lays the ingredients out in the inventory's crafting grid, or the one of a crafting table if
+//there's one close by, and crafts with them as many times as asked for or possible
+fn craft(context: &mut CommandContext, args: &[&str]) -> Result<String, CommandError> {
+    let (name, times) = match args {
+        [name] => (name, 1),
+        [name, times] => (
+            name,
+            times
+                .parse()
+                .map_err(|_| CommandError::InvalidNumber(times.to_string()))?,
+        ),
+        _ => return Err(CommandError::Usage("craft <block> [times]")),
+    };
+    let item = block(name)?;
+
+    let mut grid = if near_crafting_table(context) {
+        CraftingGrid::table()
+    } else {
+        CraftingGrid::inventory()
+    };
+    let recipes: Vec<_> = context.recipes.recipes_for(item).collect();
+    if recipes.is_empty() {
+        return Err(CommandError::UnknownRecipe(name.to_string()));
+    }
+    if !recipes.iter().any(|recipe| recipe.fits(grid.size())) {
+        return Err(CommandError::NeedsCraftingTable(name.to_string()));
+    }
+
+    let inventory = &mut context.player.inventory;
+    let (mut crafted, mut lost) = (0, 0);
+    for _ in 0..times {
+        if !recipes.iter().any(|recipe| grid.place(recipe, inventory)) {
+            break;
+        }
+        let Some(output) = grid.craft(context.recipes) else {
+            break;
+        };
+        crafted += output.count;
+        lost += inventory.add(output.item, output.count);
+        for stack in grid.take_all() {
+            lost += inventory.add(stack.item, stack.count);
+        }
+    }
+
+    match (crafted, lost) {
+        (0, _) => Err(CommandError::MissingIngredients(name.to_string())),
+        (_, 0) => Ok(format!("Crafted {} {}", crafted, name)),
+        _ => Ok(format!(
+            "Crafted {} {}, {} items didn't fit in the inventory",
+            crafted, name, lost
+        )),
+    }
+}
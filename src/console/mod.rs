@@ -0,0 +1,398 @@
+mod commands;
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::renderer::debug::DebugSettings;
+use crate::world::crafting::RecipeBook;
+use crate::world::edit::{EditError, Editor};
+use crate::world::player::Player;
+use crate::world::World;
+
+//lines of output kept around, the oldest ones are dropped first
+const OUTPUT_LENGTH: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum CommandError {
+    #[error("Unknown command `{0}`, try /help")]
+    UnknownCommand(String),
+    #[error("Usage: /{0}")]
+    Usage(&'static str),
+    #[error("`{0}` is not a number")]
+    InvalidNumber(String),
+    #[error("`{0}` is outside of the world")]
+    OutsideOfWorld(String),
+    #[error("Unknown block `{0}`")]
+    UnknownBlock(String),
+    #[error("`{0}` can't be held")]
+    NotAnItem(String),
+    #[error("`{0}` is not a valid count")]
+    InvalidCount(String),
+    #[error("Unknown game mode `{0}`")]
+    UnknownGameMode(String),
+    #[error("There's no recipe for `{0}`")]
+    UnknownRecipe(String),
+    #[error("Making `{0}` needs a crafting table nearby")]
+    NeedsCraftingTable(String),
+    #[error("Not enough ingredients for `{0}`")]
+    MissingIngredients(String),
+    #[error{"{0}"}]
+    EditError(#[from] EditError),
+}
+
+//everything a command can change
+pub struct CommandContext<'a> {
+    pub world: &'a mut World,
+    pub player: &'a mut Player,
+    pub editor: &'a mut Editor,
+    pub debug: &'a mut DebugSettings,
+    pub recipes: &'a RecipeBook,
+}
+
+//runs the command with the arguments after its name, returning what to print
+pub type CommandFn = fn(&mut CommandContext, &[&str]) -> Result<String, CommandError>;
+
+pub struct Command {
+    //the arguments the command takes after its name, shown when they're wrong and in /help
+    pub usage: &'static str,
+    pub run: CommandFn,
+}
+
+//commands typed after `/`, the line being typed is only there while the console is open
+pub struct Console {
+    commands: BTreeMap<&'static str, Command>,
+    output: Vec<String>,
+    input: Option<String>,
+}
+
+impl Console {
+    //with every built in command registered
+    pub fn new() -> Self {
+        let mut console = Self {
+            commands: BTreeMap::new(),
+            output: Vec::new(),
+            input: None,
+        };
+        commands::register(&mut console);
+        console
+    }
+
+    //replaces the command if there already is one with the name
+    pub fn register(&mut self, name: &'static str, usage: &'static str, run: CommandFn) {
+        self.commands.insert(name, Command { usage, run });
+    }
+
+    pub fn commands(&self) -> impl Iterator<Item = (&'static str, &Command)> {
+        self.commands.iter().map(|(name, command)| (*name, command))
+    }
+
+    //runs a line like `/tp 0 40 0`, the slash is optional. whatever the command prints or the
+    //error is added to the output too
+    pub fn execute(
+        &mut self,
+        line: &str,
+        context: &mut CommandContext,
+    ) -> Result<String, CommandError> {
+        let line = line.trim();
+        let line = line.strip_prefix('/').unwrap_or(line);
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or("");
+        let args: Vec<&str> = words.collect();
+
+        let result = match name {
+            "help" => Ok(self.help()),
+            _ => match self.commands.get(name) {
+                Some(command) => (command.run)(context, &args),
+                None => Err(CommandError::UnknownCommand(name.to_string())),
+            },
+        };
+
+        match &result {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => self.print(output.clone()),
+            Err(err) => self.print(err.to_string()),
+        }
+        result
+    }
+
+    fn help(&self) -> String {
+        let usages: Vec<String> = self
+            .commands
+            .iter()
+            .map(|(name, command)| {
+                format!("/{} {}", name, command.usage)
+                    .trim_end()
+                    .to_string()
+            })
+            .collect();
+        usages.join("\n")
+    }
+
+    pub fn print(&mut self, text: String) {
+        self.output.extend(text.lines().map(str::to_string));
+        if self.output.len() > OUTPUT_LENGTH {
+            self.output.drain(..self.output.len() - OUTPUT_LENGTH);
+        }
+    }
+
+    //the newest line last
+    pub fn output(&self) -> &[String] {
+        &self.output
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn open(&mut self) {
+        self.input = Some(String::new());
+    }
+
+    pub fn close(&mut self) {
+        self.input = None;
+    }
+
+    //the line being typed while the console is open
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_deref()
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        if let Some(input) = &mut self.input {
+            input.push(c);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(input) = &mut self.input {
+            input.pop();
+        }
+    }
+
+    //runs the typed line and closes the console
+    pub fn submit(&mut self, context: &mut CommandContext) {
+        if let Some(input) = self.input.take() {
+            if !input.trim().is_empty() {
+                let _ = self.execute(&input, context);
+            }
+        }
+    }
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::math;
+    use crate::world::block::Block;
+    use crate::world::generation::VoidGenerator;
+    use crate::world::player::GameMode;
+    use crate::world::position::{BlockPos, ChunkPos};
+
+    struct Game {
+        world: World,
+        player: Player,
+        editor: Editor,
+        debug: DebugSettings,
+        recipes: RecipeBook,
+    }
+
+    impl Game {
+        fn new() -> Self {
+            let mut world = World::new(42, Box::new(VoidGenerator));
+            world.generate_chunk(ChunkPos::new(0, 0));
+            Self {
+                world,
+                player: Player::new(math::Vec3::new(1.0, 20.0, 1.0)),
+                editor: Editor::new(),
+                debug: DebugSettings::new(),
+                recipes: RecipeBook::new(),
+            }
+        }
+
+        fn run(&mut self, console: &mut Console, line: &str) -> Result<String, CommandError> {
+            let mut context = CommandContext {
+                world: &mut self.world,
+                player: &mut self.player,
+                editor: &mut self.editor,
+                debug: &mut self.debug,
+                recipes: &self.recipes,
+            };
+            console.execute(line, &mut context)
+        }
+    }
+
+    #[test]
+    fn tp_moves_to_absolute_and_relative_positions() {
+        let (mut game, mut console) = (Game::new(), Console::new());
+        game.run(&mut console, "/tp 5 30 -2.5").unwrap();
+        let position = &game.player.position;
+        assert_eq!((position.x, position.y, position.z), (5.0, 30.0, -2.5));
+
+        game.run(&mut console, "tp ~ ~-10 ~2.5").unwrap();
+        let position = &game.player.position;
+        assert_eq!((position.x, position.y, position.z), (5.0, 20.0, 0.0));
+    }
+
+    #[test]
+    fn tp_stays_in_the_world() {
+        let (mut game, mut console) = (Game::new(), Console::new());
+        for line in ["/tp NaN 0 0", "/tp 0 inf 0", "/tp 0 0 ~-infinity"] {
+            assert!(matches!(
+                game.run(&mut console, line),
+                Err(CommandError::InvalidNumber(_))
+            ));
+        }
+        for line in ["/tp 1e30 0 0", "/tp 0 0 -1000001", "/tp 2000000 20 0"] {
+            assert!(matches!(
+                game.run(&mut console, line),
+                Err(CommandError::OutsideOfWorld(_))
+            ));
+        }
+        //below the bottom or above the top of the world
+        for line in [
+            "/tp 0 29000000 0",
+            "/tp ~ ~1e9 ~",
+            "/tp 0 -0.5 0",
+            "/tp 0 51 0",
+        ] {
+            assert!(matches!(
+                game.run(&mut console, line),
+                Err(CommandError::OutsideOfWorld(_))
+            ));
+        }
+        let position = &game.player.position;
+        assert_eq!((position.x, position.y, position.z), (1.0, 20.0, 1.0));
+
+        game.run(&mut console, "/tp -1000000 50 1000000").unwrap();
+        let position = &game.player.position;
+        assert_eq!(
+            (position.x, position.y, position.z),
+            (-1_000_000.0, 50.0, 1_000_000.0)
+        );
+    }
+
+    #[test]
+    fn simple_commands() {
+        let (mut game, mut console) = (Game::new(), Console::new());
+        game.run(&mut console, "/time set noon").unwrap();
+        assert_eq!(game.world.time().ticks(), 6000);
+        game.run(&mut console, "/time set 123").unwrap();
+        assert_eq!(game.world.time().ticks(), 123);
+
+        game.run(&mut console, "/gamemode s").unwrap();
+        assert_eq!(game.player.game_mode, GameMode::Survival);
+        game.run(&mut console, "/gamemode creative").unwrap();
+        assert_eq!(game.player.game_mode, GameMode::Creative);
+
+        assert_eq!(game.run(&mut console, "/seed").unwrap(), "Seed: 42");
+        assert_eq!(console.output().last().unwrap(), "Seed: 42");
+    }
+
+    #[test]
+    fn fill_can_be_undone_and_redone() {
+        let (mut game, mut console) = (Game::new(), Console::new());
+        assert_eq!(
+            game.run(&mut console, "/fill 0 0 0 1 1 1 stone").unwrap(),
+            "Changed 8 blocks"
+        );
+        let (first, last) = (BlockPos::new(0, 0, 0), BlockPos::new(1, 1, 1));
+        assert_eq!(game.world.block(first), Block::Stone);
+        assert_eq!(game.world.block(last), Block::Stone);
+
+        game.run(&mut console, "/undo").unwrap();
+        assert_eq!(game.world.block(first), Block::Air);
+        assert_eq!(game.world.block(last), Block::Air);
+        assert_eq!(game.run(&mut console, "/undo").unwrap(), "Nothing to undo");
+
+        game.run(&mut console, "/redo").unwrap();
+        assert_eq!(game.world.block(first), Block::Stone);
+        assert_eq!(game.world.block(last), Block::Stone);
+    }
+
+    #[test]
+    fn wrong_commands_are_reported() {
+        let (mut game, mut console) = (Game::new(), Console::new());
+        assert!(matches!(
+            game.run(&mut console, "/tp 1 2"),
+            Err(CommandError::Usage("tp <x> <y> <z>"))
+        ));
+        assert!(matches!(
+            game.run(&mut console, "/time noon"),
+            Err(CommandError::Usage(_))
+        ));
+        assert!(matches!(
+            game.run(&mut console, "/gamemode spectator"),
+            Err(CommandError::UnknownGameMode(_))
+        ));
+        assert!(matches!(
+            game.run(&mut console, "/fill 0 0 0 1 1 1 marble"),
+            Err(CommandError::UnknownBlock(_))
+        ));
+        assert!(matches!(
+            game.run(&mut console, "/fly"),
+            Err(CommandError::UnknownCommand(name)) if name == "fly"
+        ));
+        //errors are printed too
+        assert_eq!(
+            console.output().last().unwrap(),
+            "Unknown command `fly`, try /help"
+        );
+    }
+
+    #[test]
+    fn give_adds_items_to_the_inventory() {
+        let (mut game, mut console) = (Game::new(), Console::new());
+        game.player.inventory.clear();
+        assert_eq!(
+            game.run(&mut console, "/give stone 100").unwrap(),
+            "Gave 100 stone"
+        );
+        assert_eq!(game.player.inventory.count(Block::Stone), 100);
+        game.run(&mut console, "/give dirt").unwrap();
+        assert_eq!(game.player.inventory.count(Block::Dirt), 1);
+
+        //blocks which drop nothing when broken can still be given
+        for (line, item) in [
+            ("/give glass", Block::Glass),
+            ("/give leaves 3", Block::Leaves),
+        ] {
+            game.run(&mut console, line).unwrap();
+            assert!(game.player.inventory.count(item) > 0);
+        }
+        for line in ["/give air", "/give water 5", "/give lava"] {
+            assert!(matches!(
+                game.run(&mut console, line),
+                Err(CommandError::NotAnItem(_))
+            ));
+        }
+        assert!(matches!(
+            game.run(&mut console, "/give stone 0"),
+            Err(CommandError::InvalidCount(_))
+        ));
+        assert!(matches!(
+            game.run(&mut console, "/give stone lots"),
+            Err(CommandError::InvalidNumber(_))
+        ));
+        assert_eq!(game.player.inventory.count(Block::Stone), 100);
+    }
+
+    #[test]
+    fn only_the_newest_output_is_kept() {
+        let mut console = Console::new();
+        for line in 0..OUTPUT_LENGTH + 10 {
+            console.print(line.to_string());
+        }
+        console.print("a\nb".to_string());
+        let output = console.output();
+        assert_eq!(output.len(), OUTPUT_LENGTH);
+        assert_eq!(output[0], "12");
+        assert_eq!(output[OUTPUT_LENGTH - 2..], ["a", "b"]);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod config;
+pub mod console;
+pub mod renderer;
+pub mod utils;
+pub mod world;
@@ -1,20 +1,31 @@
 extern crate glfw;
 use self::glfw::Context;
+use std::path::Path;
 use std::sync::mpsc::Receiver;
 
 extern crate gl;
 
-mod renderer;
-mod utils;
-use crate::renderer::Renderer;
+use crafty_mod::config::Config;
+use crafty_mod::renderer::Renderer;
+use crafty_mod::world::crafting::RecipeBook;
+use crafty_mod::world::World;
 //use crate::utils::math;
 
 //use opensimplex_noise_rs::OpenSimplexNoise;
 
+//the world is updated 20 times a second regardless of the frame rate
+const TICK_LENGTH: f64 = 1.0 / 20.0;
+//the most ticks run in a single frame
+const MAX_CATCH_UP_TICKS: f64 = 5.0;
+
 fn main() {
     //let noise_generator = OpenSimplexNoise::new(Some(883_279_212_983_182_319));
     //let scale = 0.044;
 
+    let config = Config::load(Path::new("config.txt")).expect("Cannot load config");
+    let recipes =
+        RecipeBook::load(Path::new("src/resources/recipes.txt")).expect("Cannot load recipes");
+
     let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
     glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
     glfw.window_hint(glfw::WindowHint::OpenGlProfile(
@@ -22,7 +33,7 @@ fn main() {
     ));
 
     let (mut window, events) = glfw
-        .create_window(800, 600, "Rust rotating cube!", glfw::WindowMode::Windowed)
+        .create_window(800, 600, "crafty", glfw::WindowMode::Windowed)
         .expect("Failed to create GLFW window.");
 
     window.make_current();
@@ -30,18 +41,39 @@ fn main() {
     window.set_cursor_mode(glfw::CursorMode::Disabled);
     gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
 
-    let mut renderer = Renderer::new().expect("Cannot create renderer");
+    let world = if config.world.is_empty() {
+        let generator = config
+            .world_generator()
+            .expect("Cannot create world generator");
+        let mut world = World::new(config.seed, generator);
+        world.time_mut().set(config.start_time);
+        world
+    } else {
+        World::load(Path::new(&config.world)).expect("Cannot load the saved world")
+    };
+    let mut renderer = Renderer::new(&config, world, recipes).expect("Cannot create renderer");
+    let (width, height) = window.get_framebuffer_size();
+    renderer.resize(width, height);
 
     let mut delta_time: f64 = 0.01;
-    let mut last_frame: f64 = 0.0;
+    //so that loading doesn't count as the first frame
+    let mut last_frame: f64 = glfw::Glfw::get_time(&glfw);
+    let mut tick_accumulator: f64 = 0.0;
 
     unsafe {
-        //gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
         gl::Enable(gl::CULL_FACE);
     }
     while !window.should_close() {
         process_input(&mut window, &mut renderer, delta_time);
-        process_events(&events, &mut renderer);
+        process_events(&mut window, &events, &mut renderer);
+
+        //after a stall, like dragging the window, the world skips ahead instead of catching up
+        //on every tick it missed at once
+        tick_accumulator = (tick_accumulator + delta_time).min(MAX_CATCH_UP_TICKS * TICK_LENGTH);
+        while tick_accumulator >= TICK_LENGTH {
+            renderer.tick();
+            tick_accumulator -= TICK_LENGTH;
+        }
 
         renderer.clear_screen();
         renderer.draw();
@@ -56,19 +88,23 @@ fn main() {
 }
 
 fn process_input(window: &mut glfw::Window, renderer: &mut Renderer, delta_time: f64) {
-    if window.get_key(glfw::Key::Escape) == glfw::Action::Press {
-        window.set_should_close(true)
-    } else {
-        renderer.process_input(window, delta_time);
-    }
+    renderer.process_input(window, delta_time);
 }
 
-fn process_events(events: &Receiver<(f64, glfw::WindowEvent)>, renderer: &mut Renderer) {
+fn process_events(
+    window: &mut glfw::Window,
+    events: &Receiver<(f64, glfw::WindowEvent)>,
+    renderer: &mut Renderer,
+) {
     for (_, event) in glfw::flush_messages(events) {
         match event {
-            glfw::WindowEvent::FramebufferSize(width, height) => unsafe {
-                gl::Viewport(0, 0, width, height)
-            },
+            //escape closes the console first if it's open
+            glfw::WindowEvent::Key(glfw::Key::Escape, _, glfw::Action::Press, _)
+                if !renderer.console_open() =>
+            {
+                window.set_should_close(true)
+            }
+            glfw::WindowEvent::FramebufferSize(width, height) => renderer.resize(width, height),
             _ => {
                 renderer.process_events(event);
             }
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+
+use crate::world::Block;
+
+//size of a single block texture in pixels
+pub const TILE_SIZE: u32 = 16;
+pub const TILES_PER_ROW: u32 = 4;
+
+fn tile_rows() -> u32 {
+    (Block::ALL.len() as u32).div_ceil(TILES_PER_ROW)
+}
+
+//texture coordinates of the block's tile as (u0, v0, u1, v1), v0 is the top row of the tile
+pub fn tile_uvs(block: Block) -> [f32; 4] {
+    let index = block.texture_index() as u32;
+    let width = 1.0 / TILES_PER_ROW as f32;
+    let height = 1.0 / tile_rows() as f32;
+    let u = (index % TILES_PER_ROW) as f32 * width;
+    let v = (index / TILES_PER_ROW) as f32 * height;
+    [u, v, u + width, v + height]
+}
+
+//cheap deterministic per pixel noise so the generated textures aren't flat colours
+fn pixel_noise(x: u32, y: u32, seed: u32) -> f32 {
+    let mut hash = x
+        .wrapping_mul(374_761_393)
+        .wrapping_add(y.wrapping_mul(668_265_263))
+        .wrapping_add(seed.wrapping_mul(2_246_822_519));
+    hash = (hash ^ (hash >> 13)).wrapping_mul(1_274_126_177);
+    (hash ^ (hash >> 16)) as f32 / u32::MAX as f32
+}
+
+fn shade(colour: [u8; 4], amount: f32) -> Rgba<u8> {
+    let channel = |value: u8| (value as f32 * amount).clamp(0.0, 255.0) as u8;
+    Rgba([
+        channel(colour[0]),
+        channel(colour[1]),
+        channel(colour[2]),
+        colour[3],
+    ])
+}
+
+fn load_tile(filepath: &Path) -> Option<RgbaImage> {
+    let img = image::open(filepath).ok()?.into_rgba8();
+    Some(imageops::resize(&img, TILE_SIZE, TILE_SIZE, FilterType::Triangle))
+}
+
+fn generate_tile(block: Block) -> RgbaImage {
+    let colour = block.colour();
+    let seed = block.texture_index() as u32;
+    let last = TILE_SIZE - 1;
+
+    RgbaImage::from_fn(TILE_SIZE, TILE_SIZE, |x, y| {
+        let noise = pixel_noise(x, y, seed);
+        let border = x == 0 || y == 0 || x == last || y == last;
+        match block {
+            Block::Air => Rgba([0, 0, 0, 0]),
+            //a frame with a clear middle and a small highlight in the corner
+            Block::Glass => {
+                if border || (x + y == 5 && x > 1 && y > 1) {
+                    shade(colour, 1.0)
+                } else {
+                    Rgba([0, 0, 0, 0])
+                }
+            }
+            Block::StainedGlass => {
+                let alpha = if border { 255 } else { colour[3] };
+                shade([colour[0], colour[1], colour[2], alpha], 0.9 + noise * 0.2)
+            }
+            Block::Leaves => {
+                if noise < 0.3 {
+                    Rgba([0, 0, 0, 0])
+                } else {
+                    shade(colour, 0.7 + noise * 0.5)
+                }
+            }
+            //a stick in the middle of the tile with a flame on top
+            Block::Torch => {
+                let middle = x == TILE_SIZE / 2 - 1 || x == TILE_SIZE / 2;
+                if !middle || y < 5 {
+                    Rgba([0, 0, 0, 0])
+                } else if y < 8 {
+                    shade(colour, 0.9 + noise * 0.2)
+                } else {
+                    Rgba([110, 80, 45, 255])
+                }
+            }
+            //a few stalks of different heights
+            Block::Wheat(_) => {
+                let stalk_height = [9, 13, 7, 11][(x / 4) as usize % 4];
+                if x % 4 == 1 && TILE_SIZE - y <= stalk_height {
+                    shade(colour, 0.8 + noise * 0.4)
+                } else {
+                    Rgba([0, 0, 0, 0])
+                }
+            }
+            //a green stem with a round blossom on top
+            Block::Flower => {
+                let (dx, dy) = (x as i32 - 7, y as i32 - 5);
+                if dx * dx + dy * dy <= 6 {
+                    shade(colour, 0.8 + noise * 0.4)
+                } else if (x == 7 || x == 8) && y > 5 {
+                    shade([60, 130, 40, 255], 0.8 + noise * 0.4)
+                } else {
+                    Rgba([0, 0, 0, 0])
+                }
+            }
+            //specks of the ore's colour in stone
+            _ if block.is_ore() => {
+                if pixel_noise(x / 2, y / 2, seed) > 0.75 {
+                    shade(colour, 0.8 + noise * 0.4)
+                } else {
+                    shade(Block::Stone.colour(), 0.85 + noise * 0.3)
+                }
+            }
+            //planks with a darker grid on top
+            Block::CraftingTable => {
+                if x % 5 == 0 || y % 5 == 0 {
+                    shade(colour, 0.55 + noise * 0.1)
+                } else {
+                    shade(colour, 0.9 + noise * 0.2)
+                }
+            }
+            //rings of the bark
+            Block::Log => shade(colour, if x % 5 == 0 { 0.7 } else { 0.9 + noise * 0.2 }),
+            _ => shade(colour, 0.85 + noise * 0.3),
+        }
+    })
+}
+
+//every block texture packed into a single image, tiles are taken from the resources folder if a
+//texture exists for the block and generated from the block's colour otherwise
+pub fn build_atlas() -> RgbaImage {
+    let mut atlas = RgbaImage::new(TILES_PER_ROW * TILE_SIZE, tile_rows() * TILE_SIZE);
+
+    for block in Block::ALL {
+        let tile = match block {
+            Block::Grass => load_tile(Path::new("src/resources/test_grass.png")),
+            Block::Stone => load_tile(Path::new("src/resources/stone.png")),
+            _ => None,
+        }
+        .unwrap_or_else(|| generate_tile(block));
+
+        let index = block.texture_index() as u32;
+        imageops::replace(
+            &mut atlas,
+            &tile,
+            ((index % TILES_PER_ROW) * TILE_SIZE) as i64,
+            ((index / TILES_PER_ROW) * TILE_SIZE) as i64,
+        );
+    }
+
+    atlas
+}
@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+//frame times over the last second
+const SAMPLE_WINDOW: Duration = Duration::from_secs(1);
+
+//replaces how the world is shaded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugView {
+    Normal,
+    Normals,
+    //block light in red and sky light in blue
+    Light,
+    //brighter where more fragments were drawn on top of each other
+    Overdraw,
+}
+
+impl DebugView {
+    pub const ALL: [DebugView; 4] = [
+        DebugView::Normal,
+        DebugView::Normals,
+        DebugView::Light,
+        DebugView::Overdraw,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DebugView::Normal => "normal",
+            DebugView::Normals => "normals",
+            DebugView::Light => "light",
+            DebugView::Overdraw => "overdraw",
+        }
+    }
+
+    //the view after this one, going back to the normal one after the last
+    pub fn next(&self) -> DebugView {
+        let index = DebugView::ALL.iter().position(|view| view == self).unwrap();
+        DebugView::ALL[(index + 1) % DebugView::ALL.len()]
+    }
+
+    //the `debug_view` uniform of the world shader
+    pub fn shader_id(&self) -> i32 {
+        DebugView::ALL.iter().position(|view| view == self).unwrap() as i32
+    }
+}
+
+impl FromStr for DebugView {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DebugView::ALL
+            .into_iter()
+            .find(|view| view.name() == s)
+            .ok_or(())
+    }
+}
+
+//debug render modes, switched with the function keys or the `/debug` command
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DebugSettings {
+    pub wireframe: bool,
+    pub chunk_borders: bool,
+    pub view: DebugView,
+}
+
+impl DebugSettings {
+    pub fn new() -> Self {
+        Self {
+            wireframe: false,
+            chunk_borders: false,
+            view: DebugView::Normal,
+        }
+    }
+}
+
+impl Default for DebugSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//measures how long frames take, for the debug overlay
+pub struct FrameTimer {
+    last_frame: Option<Instant>,
+    samples: VecDeque<Duration>,
+    total: Duration,
+}
+
+impl FrameTimer {
+    pub fn new() -> Self {
+        Self {
+            last_frame: None,
+            samples: VecDeque::new(),
+            total: Duration::ZERO,
+        }
+    }
+
+    //has to be called once at the start of every frame
+    pub fn frame(&mut self) {
+        let now = Instant::now();
+        if let Some(last_frame) = self.last_frame {
+            let frame_time = now - last_frame;
+            self.samples.push_back(frame_time);
+            self.total += frame_time;
+            while self.total > SAMPLE_WINDOW && self.samples.len() > 1 {
+                self.total -= self.samples.pop_front().unwrap();
+            }
+        }
+        self.last_frame = Some(now);
+    }
+
+    pub fn fps(&self) -> f32 {
+        if self.total.is_zero() {
+            return 0.0;
+        }
+        self.samples.len() as f32 / self.total.as_secs_f32()
+    }
+
+    //average and longest frame time in milliseconds
+    pub fn frame_times(&self) -> (f32, f32) {
+        if self.samples.is_empty() {
+            return (0.0, 0.0);
+        }
+        let average = self.total.as_secs_f32() / self.samples.len() as f32;
+        let longest = self.samples.iter().max().unwrap().as_secs_f32();
+        (average * 1000.0, longest * 1000.0)
+    }
+}
+
+impl Default for FrameTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,280 @@
+use std::path::Path;
+
+use image::{ImageError, Rgba, RgbaImage};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FontError {
+    #[error{"{0}"}]
+    ImageError(#[from] ImageError),
+    #[error("Glyphs can't be {0}x{1} pixels")]
+    InvalidGlyphSize(u32, u32),
+    #[error("The font only has {0} glyphs, it needs at least {1}")]
+    TooFewGlyphs(u32, u32),
+}
+
+//a monospaced bitmap font laid out in a grid, the characters going left to right and then top
+//to bottom starting with the space. everything here is plain cpu work so text can be laid out
+//and measured without a window
+pub struct Font {
+    image: RgbaImage,
+    glyph_width: u32,
+    glyph_height: u32,
+    columns: u32,
+    glyph_count: u32,
+}
+
+const FIRST_CHAR: u32 = ' ' as u32;
+//drawn for characters the font doesn't have
+const REPLACEMENT: char = '?';
+
+//a glyph's rectangle on the screen and in the font texture, as (left, top, right, bottom)
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlyphQuad {
+    pub position: [f32; 4],
+    pub uv: [f32; 4],
+}
+
+impl Font {
+    //the image is white where the glyphs are, greyscale images are used as the alpha
+    pub fn load(path: &Path, glyph_width: u32, glyph_height: u32) -> Result<Self, FontError> {
+        let grey = image::open(path)?.into_luma_alpha8();
+        let mut image = RgbaImage::new(grey.width(), grey.height());
+        for (x, y, pixel) in grey.enumerate_pixels() {
+            let alpha = (pixel[0] as u32 * pixel[1] as u32 / 255) as u8;
+            image.put_pixel(x, y, Rgba([255, 255, 255, alpha]));
+        }
+        Self::from_image(image, glyph_width, glyph_height)
+    }
+
+    //the font needs every glyph up to the replacement one and the solid one after them
+    pub fn from_image(
+        image: RgbaImage,
+        glyph_width: u32,
+        glyph_height: u32,
+    ) -> Result<Self, FontError> {
+        if glyph_width == 0 || glyph_height == 0 {
+            return Err(FontError::InvalidGlyphSize(glyph_width, glyph_height));
+        }
+        let columns = image.width() / glyph_width;
+        let rows = image.height() / glyph_height;
+        let needed = REPLACEMENT as u32 - FIRST_CHAR + 2;
+        if columns * rows < needed {
+            return Err(FontError::TooFewGlyphs(columns * rows, needed));
+        }
+
+        let mut font = Self {
+            image,
+            glyph_width,
+            glyph_height,
+            columns,
+            glyph_count: columns * rows,
+        };
+        font.fill_solid_glyph();
+        Ok(font)
+    }
+
+    //the last glyph of the grid, usually the unprintable delete, is made solid so that plain
+    //rectangles like backgrounds can be drawn with the same texture
+    fn fill_solid_glyph(&mut self) {
+        let [left, top, _, _] = self.glyph_pixels(self.glyph_count - 1);
+        for y in top..top + self.glyph_height {
+            for x in left..left + self.glyph_width {
+                self.image.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+            }
+        }
+    }
+
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    pub fn glyph_size(&self) -> (u32, u32) {
+        (self.glyph_width, self.glyph_height)
+    }
+
+    fn glyph_index(&self, c: char) -> u32 {
+        let index = (c as u32).wrapping_sub(FIRST_CHAR);
+        //the last glyph is the solid one
+        if index < self.glyph_count - 1 {
+            index
+        } else {
+            REPLACEMENT as u32 - FIRST_CHAR
+        }
+    }
+
+    fn glyph_pixels(&self, index: u32) -> [u32; 4] {
+        let left = index % self.columns * self.glyph_width;
+        let top = index / self.columns * self.glyph_height;
+        [left, top, left + self.glyph_width, top + self.glyph_height]
+    }
+
+    fn uv(&self, [left, top, right, bottom]: [u32; 4]) -> [f32; 4] {
+        let (width, height) = (self.image.width() as f32, self.image.height() as f32);
+        [
+            left as f32 / width,
+            top as f32 / height,
+            right as f32 / width,
+            bottom as f32 / height,
+        ]
+    }
+
+    //texture coordinates of the glyph as (u0, v0, u1, v1), v0 is the top row of the glyph
+    pub fn glyph_uv(&self, c: char) -> [f32; 4] {
+        self.uv(self.glyph_pixels(self.glyph_index(c)))
+    }
+
+    //texture coordinates of the middle of the solid glyph, so the edges never bleed in
+    pub fn solid_uv(&self) -> [f32; 4] {
+        let [left, top, right, bottom] = self.glyph_pixels(self.glyph_count - 1);
+        let (x, y) = ((left + right) / 2, (top + bottom) / 2);
+        self.uv([x, y, x + 1, y + 1])
+    }
+
+    //size of the text in pixels, lines are split on `\n`
+    pub fn measure(&self, text: &str) -> (u32, u32) {
+        let lines = text.split('\n');
+        let (count, widest) = lines.fold((0, 0), |(count, widest), line| {
+            (count + 1, widest.max(line.chars().count() as u32))
+        });
+        (widest * self.glyph_width, count * self.glyph_height)
+    }
+
+    //splits the text into lines no wider than `max_width` pixels, breaking between words where
+    //possible and in the middle of words which don't fit on a line by themselves
+    pub fn wrap(&self, text: &str, max_width: u32) -> Vec<String> {
+        let max_chars = (max_width / self.glyph_width).max(1) as usize;
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let mut line = String::new();
+            let mut line_chars = 0;
+            for word in paragraph.split(' ') {
+                let mut word: Vec<char> = word.chars().collect();
+                //the word goes on the current line if it fits, with a space in front of it
+                let space = if line_chars == 0 { 0 } else { 1 };
+                if line_chars + space + word.len() <= max_chars {
+                    if space == 1 {
+                        line.push(' ');
+                    }
+                    line.extend(word.iter());
+                    line_chars += space + word.len();
+                    continue;
+                }
+
+                if line_chars > 0 {
+                    lines.push(std::mem::take(&mut line));
+                }
+                while word.len() > max_chars {
+                    lines.push(word.drain(..max_chars).collect());
+                }
+                line_chars = word.len();
+                line.extend(word);
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    //a quad for every visible character with the text's top left corner at (`x`, `y`), in
+    //pixels with y going down. glyphs are `scale` times their size in the font
+    pub fn layout(&self, text: &str, x: f32, y: f32, scale: f32) -> Vec<GlyphQuad> {
+        let (width, height) = (
+            self.glyph_width as f32 * scale,
+            self.glyph_height as f32 * scale,
+        );
+        let mut quads = Vec::new();
+        for (row, line) in text.split('\n').enumerate() {
+            let top = y + row as f32 * height;
+            for (column, c) in line.chars().enumerate() {
+                if c == ' ' {
+                    continue;
+                }
+                let left = x + column as f32 * width;
+                quads.push(GlyphQuad {
+                    position: [left, top, left + width, top + height],
+                    uv: self.glyph_uv(c),
+                });
+            }
+        }
+        quads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //2x3 pixel glyphs in a 16x6 grid, from the space to the delete
+    fn font() -> Font {
+        Font::from_image(RgbaImage::new(32, 18), 2, 3).unwrap()
+    }
+
+    #[test]
+    fn fonts_need_every_glyph_up_to_the_replacement() {
+        assert!(matches!(
+            Font::from_image(RgbaImage::new(32, 18), 0, 3),
+            Err(FontError::InvalidGlyphSize(0, 3))
+        ));
+        assert!(matches!(
+            Font::from_image(RgbaImage::new(1, 1), 2, 3),
+            Err(FontError::TooFewGlyphs(0, 33))
+        ));
+        //just the replacement and the solid glyph after it
+        assert!(Font::from_image(RgbaImage::new(66, 3), 2, 3).is_ok());
+        assert!(matches!(
+            Font::from_image(RgbaImage::new(64, 3), 2, 3),
+            Err(FontError::TooFewGlyphs(32, 33))
+        ));
+    }
+
+    #[test]
+    fn measures_the_widest_line() {
+        let font = font();
+        assert_eq!(font.measure(""), (0, 3));
+        assert_eq!(font.measure("abc"), (6, 3));
+        assert_eq!(font.measure("a\nabcd\n"), (8, 9));
+    }
+
+    #[test]
+    fn wraps_between_words() {
+        let font = font();
+        assert_eq!(font.wrap("hello big world", 18), ["hello big", "world"]);
+        assert_eq!(font.wrap("one\ntwo three", 18), ["one", "two three"]);
+    }
+
+    #[test]
+    fn breaks_words_longer_than_a_line() {
+        let font = font();
+        assert_eq!(font.wrap("abcdefg", 6), ["abc", "def", "g"]);
+        assert_eq!(font.wrap("ab abcdefg", 6), ["ab", "abc", "def", "g"]);
+        //at least one character fits on every line
+        assert_eq!(font.wrap("abc", 1), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn wraps_empty_text_and_repeated_spaces() {
+        let font = font();
+        assert_eq!(font.wrap("", 10), [""]);
+        assert_eq!(font.wrap("a  b", 10), ["a  b"]);
+        assert_eq!(font.wrap("\n", 10), ["", ""]);
+    }
+
+    #[test]
+    fn lays_out_visible_characters() {
+        let font = font();
+        let quads = font.layout("a b\n\u{e9}", 10.0, 20.0, 2.0);
+        //the space is skipped but still moves the next glyph along
+        assert_eq!(quads.len(), 3);
+        assert_eq!(quads[0].position, [10.0, 20.0, 14.0, 26.0]);
+        assert_eq!(quads[1].position, [18.0, 20.0, 22.0, 26.0]);
+        assert_eq!(quads[2].position, [10.0, 26.0, 14.0, 32.0]);
+
+        assert_eq!(quads[0].uv, font.glyph_uv('a'));
+        //characters the font doesn't have are drawn as the replacement
+        assert_eq!(quads[2].uv, font.glyph_uv('?'));
+        assert_eq!(
+            font.glyph_uv('?'),
+            [30.0 / 32.0, 3.0 / 18.0, 1.0, 6.0 / 18.0]
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use crate::renderer::buffer::Buffer;
+use crate::renderer::program::ShaderProgram;
+use crate::renderer::shader::{Shader, ShaderError};
+use crate::renderer::vertex_array::{VertexArray, VertexArrayConfiguration};
+
+use std::ffi::CStr;
+
+use cgmath::Matrix4;
+
+//coloured lines in the world like chunk borders, collected during the frame and drawn at once
+pub struct LineRenderer {
+    program: ShaderProgram,
+    vao: VertexArray,
+    vbo: Buffer,
+    vertices: Vec<f32>,
+}
+
+impl LineRenderer {
+    pub unsafe fn new() -> Result<Self, ShaderError> {
+        let vertex_shader = Shader::new("src/shaders/line.vs", gl::VERTEX_SHADER)?;
+        let fragment_shader = Shader::new("src/shaders/line.frag", gl::FRAGMENT_SHADER)?;
+        let program = ShaderProgram::new(&[vertex_shader, fragment_shader])?;
+
+        Ok(Self {
+            program,
+            vao: VertexArray::new(),
+            vbo: Buffer::new(gl::ARRAY_BUFFER),
+            vertices: Vec::new(),
+        })
+    }
+
+    pub fn line(&mut self, from: [f32; 3], to: [f32; 3], colour: [f32; 3]) {
+        self.vertices.extend_from_slice(&from);
+        self.vertices.extend_from_slice(&colour);
+        self.vertices.extend_from_slice(&to);
+        self.vertices.extend_from_slice(&colour);
+    }
+
+    //draws everything added since the last time and forgets it
+    pub unsafe fn draw(&mut self, projection: &Matrix4<f32>, view: &Matrix4<f32>) {
+        if self.vertices.is_empty() {
+            return;
+        }
+
+        self.program.bind();
+        self.program.set_mat4(c_str!("projection"), projection);
+        self.program.set_mat4(c_str!("view"), view);
+
+        self.vao.bind();
+        self.vbo.set_data(&self.vertices, gl::STREAM_DRAW);
+        self.vao.setup_vao(VertexArrayConfiguration::XyzAndColour);
+        gl::DrawArrays(gl::LINES, 0, (self.vertices.len() / 6) as i32);
+
+        self.vertices.clear();
+    }
+}
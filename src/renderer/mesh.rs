@@ -0,0 +1,271 @@
+use crate::renderer::atlas;
+use crate::utils::math;
+use crate::world::block::RenderLayer;
+use crate::world::chunk::{CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH, MAX_LIGHT};
+use crate::world::fluid;
+use crate::world::{Block, BlockPos, ChunkPos, Direction, World};
+
+#[derive(Clone)]
+pub struct Vertex {
+    pub position: math::Vec3,
+    pub uv: [f32; 2],
+    pub normal: math::Vec3,
+    //sky and block light of the space in front of the face, from 0.0 to 1.0
+    pub light: [f32; 2],
+}
+
+impl Vertex {
+    pub fn new(position: math::Vec3, uv: [f32; 2], normal: math::Vec3, light: [f32; 2]) -> Self {
+        Self {
+            position,
+            uv,
+            normal,
+            light,
+        }
+    }
+}
+
+pub const FLOATS_PER_VERTEX: usize = 10;
+
+struct Face {
+    direction: Direction,
+    //counter-clockwise when looking at the face from outside of the block
+    corners: [[f32; 3]; 4],
+}
+
+const FACE_UVS: [[f32; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+
+const FACES: [Face; 6] = [
+    Face {
+        direction: Direction::Up,
+        corners: [
+            [0.0, 1.0, 0.0],
+            [0.0, 1.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [1.0, 1.0, 0.0],
+        ],
+    },
+    Face {
+        direction: Direction::Down,
+        corners: [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0],
+        ],
+    },
+    Face {
+        direction: Direction::North,
+        corners: [
+            [1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+        ],
+    },
+    Face {
+        direction: Direction::South,
+        corners: [
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ],
+    },
+    Face {
+        direction: Direction::West,
+        corners: [
+            [0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 1.0, 1.0],
+            [0.0, 1.0, 0.0],
+        ],
+    },
+    Face {
+        direction: Direction::East,
+        corners: [
+            [1.0, 0.0, 1.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [1.0, 1.0, 1.0],
+        ],
+    },
+];
+
+//whether the face of `block` facing `neighbour` can be seen
+fn is_face_visible(block: Block, neighbour: Block) -> bool {
+    //no faces between two blocks of water or glass
+    !neighbour.is_opaque()
+        && (!neighbour.is_same_kind(&block) || block.render_layer() == RenderLayer::Opaque)
+}
+
+//fluids which aren't covered by more of the same fluid only fill part of the block
+fn block_height(world: &World, pos: BlockPos, block: Block) -> f32 {
+    match block.fluid() {
+        Some((_, level)) if !world.block(pos.neighbour(Direction::Up)).is_same_kind(&block) => {
+            fluid::surface_height(level)
+        }
+        _ => 1.0,
+    }
+}
+
+//cpu side geometry of a chunk, only faces which aren't hidden by a neighbour are kept
+pub struct Mesh {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<i32>,
+}
+
+//a chunk is split into a mesh per render layer since every layer is drawn in a separate pass
+pub struct ChunkMeshes {
+    pub opaque: Mesh,
+    pub cutout: Mesh,
+    pub translucent: Mesh,
+}
+
+impl ChunkMeshes {
+    pub fn build(world: &World, chunk_pos: ChunkPos) -> Self {
+        let mut meshes = Self {
+            opaque: Mesh::new(),
+            cutout: Mesh::new(),
+            translucent: Mesh::new(),
+        };
+
+        if !world.is_loaded(chunk_pos) {
+            return meshes;
+        }
+
+        for y in 0..CHUNK_HEIGHT {
+            for z in 0..CHUNK_DEPTH {
+                for x in 0..CHUNK_WIDTH {
+                    let pos = chunk_pos.block_pos(x, y, z);
+                    let block = world.block(pos);
+                    if block == Block::Air {
+                        continue;
+                    }
+
+                    let height = block_height(world, pos, block);
+                    let mesh = meshes.layer_mut(block.render_layer());
+                    for face in FACES.iter() {
+                        let neighbour = pos.neighbour(face.direction);
+                        if neighbour.y < 0 || !is_face_visible(block, world.block(neighbour)) {
+                            continue;
+                        }
+
+                        let light = [
+                            world.sky_light(neighbour) as f32 / MAX_LIGHT as f32,
+                            world.block_light(neighbour) as f32 / MAX_LIGHT as f32,
+                        ];
+                        let origin = math::Vec3::new(pos.x as f32, pos.y as f32, pos.z as f32);
+                        mesh.add_face(face, &origin, height, atlas::tile_uvs(block), light);
+                    }
+                }
+            }
+        }
+
+        meshes
+    }
+
+    pub fn layer_mut(&mut self, layer: RenderLayer) -> &mut Mesh {
+        match layer {
+            RenderLayer::Opaque => &mut self.opaque,
+            RenderLayer::Cutout => &mut self.cutout,
+            RenderLayer::Translucent => &mut self.translucent,
+        }
+    }
+}
+
+impl Default for Mesh {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mesh {
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+
+    fn add_face(
+        &mut self,
+        face: &Face,
+        origin: &math::Vec3,
+        height: f32,
+        tile: [f32; 4],
+        light: [f32; 2],
+    ) {
+        let first_index = self.vertices.len() as i32;
+        let (nx, ny, nz) = face.direction.offset();
+        let [u0, v0, u1, v1] = tile;
+
+        for (corner, uv) in face.corners.iter().zip(FACE_UVS.iter()) {
+            //the top of the tile is at v0 so the face's v has to be flipped
+            let uv = [u0 + uv[0] * (u1 - u0), v1 - uv[1] * (v1 - v0)];
+            self.vertices.push(Vertex::new(
+                math::Vec3::new(
+                    origin.x + corner[0],
+                    origin.y + corner[1] * height,
+                    origin.z + corner[2],
+                ),
+                uv,
+                math::Vec3::new(nx as f32, ny as f32, nz as f32),
+                light,
+            ));
+        }
+
+        for index in [0, 1, 2, 2, 3, 0] {
+            self.indices.push(first_index + index);
+        }
+    }
+
+    //a whole cube with its lowest corner at `origin`, used for blocks which aren't part of a chunk
+    pub fn add_block(&mut self, block: Block, origin: &math::Vec3, light: [f32; 2]) {
+        for face in FACES.iter() {
+            self.add_face(face, origin, 1.0, atlas::tile_uvs(block), light);
+        }
+    }
+
+    //reorders the faces so that the ones furthest away from `eye` are drawn first, which is what
+    //blending needs to look right
+    pub fn sort_back_to_front(&mut self, eye: &math::Vec3) {
+        let distance = |quad: &[i32]| {
+            //the middle of a face is halfway between two of its opposite corners
+            let a = &self.vertices[quad[0] as usize].position;
+            let b = &self.vertices[quad[2] as usize].position;
+            let dx = (a.x + b.x) * 0.5 - eye.x;
+            let dy = (a.y + b.y) * 0.5 - eye.y;
+            let dz = (a.z + b.z) * 0.5 - eye.z;
+            dx * dx + dy * dy + dz * dz
+        };
+
+        let mut quads: Vec<(f32, &[i32])> = self
+            .indices
+            .chunks(6)
+            .map(|quad| (distance(quad), quad))
+            .collect();
+        quads.sort_by(|a, b| b.0.total_cmp(&a.0));
+        self.indices = quads.iter().flat_map(|(_, quad)| quad.iter().copied()).collect();
+    }
+
+    //interleaved vertex data in the layout expected by the chunk shader
+    pub fn vertex_data(&self) -> Vec<f32> {
+        let mut data = Vec::with_capacity(self.vertices.len() * FLOATS_PER_VERTEX);
+        for vertex in self.vertices.iter() {
+            data.extend_from_slice(&[
+                vertex.position.x,
+                vertex.position.y,
+                vertex.position.z,
+                vertex.uv[0],
+                vertex.uv[1],
+                vertex.normal.x,
+                vertex.normal.y,
+                vertex.normal.z,
+                vertex.light[0],
+                vertex.light[1],
+            ]);
+        }
+        data
+    }
+}
@@ -1,10 +1,39 @@
+//convert literals to c strings without any runtime overhead
+#[cfg(feature = "window")]
+macro_rules! c_str {
+    ($literal:expr) => {
+        CStr::from_bytes_with_nul_unchecked(concat!($literal, "\0").as_bytes())
+    };
+}
+
+//the atlas, the meshes, the text layout and the debug settings are done on the cpu and also used
+//by the tools and the console, everything else needs a window and an opengl context
+pub mod atlas;
+#[cfg(feature = "window")]
 mod buffer;
+#[cfg(feature = "window")]
 mod camera;
+#[cfg(feature = "window")]
 mod chunk;
+pub mod debug;
+pub mod font;
+pub mod mesh;
+#[cfg(feature = "window")]
+mod lines;
+#[cfg(feature = "window")]
 mod program;
+#[cfg(feature = "window")]
 mod renderer;
+#[cfg(feature = "window")]
 mod shader;
+#[cfg(feature = "window")]
+mod sky;
+#[cfg(feature = "window")]
+mod text;
+#[cfg(feature = "window")]
 mod texture;
+#[cfg(feature = "window")]
 mod vertex_array;
 
+#[cfg(feature = "window")]
 pub use renderer::Renderer;
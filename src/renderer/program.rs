@@ -4,6 +4,7 @@ use std::ffi::CStr;
 
 use cgmath::Matrix;
 use cgmath::Matrix4;
+use cgmath::Vector3;
 
 pub struct ShaderProgram {
     pub id: GLuint,
@@ -76,4 +77,17 @@ impl ShaderProgram {
             mat.as_ptr(),
         );
     }
+
+    pub unsafe fn set_float(&self, name: &CStr, value: f32) {
+        gl::Uniform1f(gl::GetUniformLocation(self.id, name.as_ptr()), value);
+    }
+
+    pub unsafe fn set_vec3(&self, name: &CStr, vec: &Vector3<f32>) {
+        gl::Uniform3f(
+            gl::GetUniformLocation(self.id, name.as_ptr()),
+            vec.x,
+            vec.y,
+            vec.z,
+        );
+    }
 }
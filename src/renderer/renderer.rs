@@ -1,33 +1,83 @@
+use crate::config::{Config, FogMode};
+use crate::console::{CommandContext, Console};
+use crate::renderer::atlas;
 use crate::renderer::camera::Camera;
 use crate::renderer::camera::Move;
-use crate::renderer::chunk::Chunk;
+use crate::renderer::chunk::{Chunk, DrawStats, MeshBuffers};
+use crate::renderer::debug::{DebugSettings, DebugView, FrameTimer};
+use crate::renderer::lines::LineRenderer;
+use crate::renderer::mesh::{ChunkMeshes, Mesh};
 use crate::renderer::program::ShaderProgram;
 use crate::renderer::shader::{Shader, ShaderError};
+use crate::renderer::sky::Sky;
+use crate::renderer::text::TextRenderer;
 use crate::renderer::texture::Texture;
 
+use std::collections::HashMap;
 use std::ffi::CStr;
-use std::path::Path;
+use std::time::{Duration, Instant};
 
 use crate::utils::math;
+use crate::world::block::RenderLayer;
+use crate::world::chunk::{CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH, MAX_LIGHT, SECTION_HEIGHT};
+use crate::world::crafting::RecipeBook;
+use crate::world::edit::Editor;
+use crate::world::inventory::HOTBAR_SIZE;
+use crate::world::player::Player;
+use crate::world::{Block, BlockPos, ChunkPos, World};
 
-use cgmath::{perspective, Deg, Matrix4};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3};
 
-//convert literals to c strings without any runtime overhead
-macro_rules! c_str {
-    ($literal:expr) => {
-        CStr::from_bytes_with_nul_unchecked(concat!($literal, "\0").as_bytes())
-    };
-}
+//how far away blocks can be broken and placed
+const REACH: f32 = 6.0;
+//lines of console output shown above the line being typed
+const CONSOLE_LINES: usize = 10;
+//how long what a command printed stays on the screen once the console is closed
+const CONSOLE_FADE: Duration = Duration::from_secs(5);
+//space between the text and the edges of the screen, in pixels of the font
+const HUD_MARGIN: f32 = 4.0;
+//size of a hotbar slot in lines of text
+const HOTBAR_SLOT_LINES: f32 = 2.0;
 
 pub struct Renderer {
     program: ShaderProgram,
+    sky: Sky,
     tex: Texture,
     camera: Camera,
-    chunk: Chunk,
+    world: World,
+    player: Player,
+    editor: Editor,
+    console: Console,
+    recipes: RecipeBook,
+    chunks: HashMap<ChunkPos, Chunk>,
+    //falling blocks and other moving things, rebuilt every frame
+    entities: MeshBuffers,
+    text: TextRenderer,
+    //in pixels
+    screen_size: (u32, u32),
+    //when the output of the last command stops being shown
+    console_shown_until: Option<Instant>,
+    show_debug_overlay: bool,
+    //scrolling zooms instead of changing the held slot while control is down
+    zoom_key_down: bool,
+    frame_timer: FrameTimer,
+    //what the world took to draw in the last frame
+    draw_stats: DrawStats,
+    debug: DebugSettings,
+    lines: LineRenderer,
+
+    ambient_strength: f32,
+    //in blocks
+    view_distance: f32,
+    fog_mode: FogMode,
 }
 
 impl Renderer {
-    pub fn new() -> Result<Self, ShaderError> {
+    pub fn new(
+        config: &Config,
+        mut world: World,
+        recipes: RecipeBook,
+    ) -> Result<Self, ShaderError> {
         unsafe {
             gl::Enable(gl::DEPTH_TEST);
 
@@ -35,11 +85,14 @@ impl Renderer {
             let fragment_shader = Shader::new("src/shaders/basic.frag", gl::FRAGMENT_SHADER)?;
             let program = ShaderProgram::new(&[vertex_shader, fragment_shader])?;
 
-            let tex = Texture::new(Path::new("src/resources/test_grass.png"));
+            let sky = Sky::new()?;
+
+            let tex = Texture::from_image(&atlas::build_atlas(), gl::NEAREST);
 
             program.set_int(c_str!("tex0"), 0);
 
-            let camera = Camera::new(math::Vec3::new(-10.0, 16.0, 11.0));
+            let spawn = math::Vec3::new(-10.0, 36.0, 11.0);
+            let camera = Camera::new(spawn.clone());
 
             let model = math::Mat4::new(1.0);
             //model.rotate(math::Vec3::new(0.5, 1.0, 0.0).normalize(), 32.0);
@@ -47,19 +100,46 @@ impl Renderer {
             let model_loc = gl::GetUniformLocation(program.id, c_str!("model").as_ptr());
             gl::UniformMatrix4fv(model_loc, 1, gl::FALSE, &model.mat[0]);
 
-            let mut chunk = Chunk::new();
-            chunk.load_cubes(math::Vec3::new(0.0, 0.0, 0.0));
+            world.time_mut().set_frozen(config.freeze_time);
+            for z in -config.render_distance..=config.render_distance {
+                for x in -config.render_distance..=config.render_distance {
+                    world.generate_chunk(ChunkPos::new(x, z));
+                }
+            }
 
             Ok(Self {
                 program,
+                sky,
                 tex,
                 camera,
-                chunk,
+                world,
+                player: Player::new(spawn),
+                editor: Editor::new(),
+                console: Console::new(),
+                recipes,
+                chunks: HashMap::new(),
+                entities: MeshBuffers::new(),
+                text: TextRenderer::new()?,
+                screen_size: (800, 600),
+                console_shown_until: None,
+                show_debug_overlay: false,
+                zoom_key_down: false,
+                frame_timer: FrameTimer::new(),
+                draw_stats: DrawStats::default(),
+                debug: DebugSettings::new(),
+                lines: LineRenderer::new()?,
+                ambient_strength: 0.45,
+                view_distance: (config.render_distance * CHUNK_WIDTH as i32) as f32,
+                fog_mode: config.fog,
             })
         }
     }
 
     pub fn process_input(&mut self, window: &glfw::Window, delta_time: f64) {
+        //keys type into the console while it's open
+        if self.console.is_open() {
+            return;
+        }
         if glfw::Window::get_key(window, glfw::Key::W) == glfw::Action::Press {
             self.camera.translate(Move::Forward, delta_time as f32);
         }
@@ -72,38 +152,567 @@ impl Renderer {
         if glfw::Window::get_key(window, glfw::Key::D) == glfw::Action::Press {
             self.camera.translate(Move::Right, delta_time as f32);
         }
+        self.zoom_key_down =
+            glfw::Window::get_key(window, glfw::Key::LeftControl) == glfw::Action::Press;
+    }
+
+    //has to be called whenever the framebuffer changes size
+    pub fn resize(&mut self, width: i32, height: i32) {
+        unsafe {
+            gl::Viewport(0, 0, width, height);
+        }
+        self.screen_size = (width.max(1) as u32, height.max(1) as u32);
+        //bigger text on high resolution screens
+        self.text.set_scale(self.screen_size.1 / 600);
+    }
+
+    pub fn console_open(&self) -> bool {
+        self.console.is_open()
     }
 
     pub fn process_events(&mut self, event: glfw::WindowEvent) {
+        if self.console.is_open() {
+            self.process_console_events(event);
+            return;
+        }
+
         match event {
+            glfw::WindowEvent::Char('/') => {
+                self.console.open();
+                self.console.type_char('/');
+            }
+            glfw::WindowEvent::Key(glfw::Key::F3, _, glfw::Action::Press, _) => {
+                self.show_debug_overlay = !self.show_debug_overlay
+            }
+            glfw::WindowEvent::Key(glfw::Key::F4, _, glfw::Action::Press, _) => {
+                self.debug.wireframe = !self.debug.wireframe
+            }
+            glfw::WindowEvent::Key(glfw::Key::F5, _, glfw::Action::Press, _) => {
+                self.debug.chunk_borders = !self.debug.chunk_borders
+            }
+            glfw::WindowEvent::Key(glfw::Key::F6, _, glfw::Action::Press, _) => {
+                self.debug.view = self.debug.view.next()
+            }
             glfw::WindowEvent::CursorPos(x, y) => self.camera.look_around(x, y),
-            glfw::WindowEvent::Scroll(_x, y) => self.camera.zoom(y as f32),
+            glfw::WindowEvent::Key(key, _, glfw::Action::Press, _)
+                if hotbar_slot(key).is_some() =>
+            {
+                self.player.inventory.select(hotbar_slot(key).unwrap())
+            }
+            glfw::WindowEvent::Scroll(_x, y) if self.zoom_key_down => self.camera.zoom(y as f32),
+            //scrolling down moves to the right like in other games
+            glfw::WindowEvent::Scroll(_x, y) if y != 0.0 => {
+                self.player.inventory.scroll(-y.signum() as i32)
+            }
+            glfw::WindowEvent::MouseButton(button, glfw::Action::Press, _) => {
+                self.use_block(button)
+            }
+            _ => (),
+        }
+    }
+
+    fn process_console_events(&mut self, event: glfw::WindowEvent) {
+        match event {
+            glfw::WindowEvent::Char(c) => self.console.type_char(c),
+            glfw::WindowEvent::Key(key, _, glfw::Action::Press | glfw::Action::Repeat, _) => {
+                match key {
+                    glfw::Key::Enter => self.submit_command(),
+                    glfw::Key::Backspace => self.console.backspace(),
+                    glfw::Key::Escape => self.console.close(),
+                    _ => (),
+                }
+            }
             _ => (),
         }
     }
 
+    //commands see the camera's position as the player's and can move it
+    fn submit_command(&mut self) {
+        self.player.position = self.eye();
+        let mut context = CommandContext {
+            world: &mut self.world,
+            player: &mut self.player,
+            editor: &mut self.editor,
+            debug: &mut self.debug,
+            recipes: &self.recipes,
+        };
+        self.console.submit(&mut context);
+        self.console_shown_until = Some(Instant::now() + CONSOLE_FADE);
+
+        let position = &self.player.position;
+        self.camera.position = Point3::new(position.x, position.y, position.z);
+    }
+
+    fn eye(&self) -> math::Vec3 {
+        math::Vec3::new(
+            self.camera.position.x,
+            self.camera.position.y,
+            self.camera.position.z,
+        )
+    }
+
+    fn look_direction(&self) -> math::Vec3 {
+        math::Vec3::new(
+            self.camera.camera_front.x,
+            self.camera.camera_front.y,
+            self.camera.camera_front.z,
+        )
+    }
+
+    //left click breaks the block the camera is looking at, right click places the held block next
+    //to it. in survival that goes through the inventory
+    fn use_block(&mut self, button: glfw::MouseButton) {
+        let hit = match self
+            .world
+            .raycast(&self.eye(), &self.look_direction(), REACH)
+        {
+            Some(hit) => hit,
+            None => return,
+        };
+
+        match button {
+            glfw::MouseButtonLeft => {
+                let broken = self.world.block(hit.pos);
+                self.world.set_block(hit.pos, Block::Air);
+                //whatever doesn't fit is lost
+                self.player.collect(broken);
+            }
+            glfw::MouseButtonRight => {
+                if let Some(face) = hit.face {
+                    //nothing is used up when there's no room for the block, like above the top
+                    //of the world
+                    let target = hit.pos.neighbour(face);
+                    if !self.world.contains(target) {
+                        return;
+                    }
+                    if let Some(block) = self.player.use_held_block() {
+                        self.world.set_block(target, block);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.world.tick();
+    }
+
     pub fn clear_screen(&mut self) {
         unsafe {
-            gl::ClearColor(0.2, 0.3, 0.3, 1.0);
+            //overdraw adds up from black
+            let [r, g, b] = if self.debug.view == DebugView::Overdraw {
+                [0.0; 3]
+            } else {
+                self.world.time().sky_colour()
+            };
+            gl::ClearColor(r, g, b, 1.0);
             gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
         }
     }
 
+    //rebuilds the meshes of every chunk whose blocks or light changed since the last frame
+    unsafe fn update_chunk_meshes(&mut self) {
+        for pos in self.world.take_dirty_chunks() {
+            let meshes = ChunkMeshes::build(&self.world, pos);
+            self.chunks
+                .entry(pos)
+                .or_insert_with(|| Chunk::new())
+                .upload(meshes);
+        }
+    }
+
     pub fn draw(&mut self) {
         unsafe {
-            gl::ActiveTexture(gl::TEXTURE0);
-            self.tex.bind();
-            self.program.bind();
+            self.frame_timer.frame();
+            self.draw_stats = DrawStats::default();
+            self.update_chunk_meshes();
 
-            let projection: Matrix4<f32> =
-                perspective(Deg(self.camera.fov), (800.0 / 600.0) as f32, 0.1, 100.0);
-            self.program.set_mat4(c_str!("projection"), &projection);
+            //the far plane is a bit past the fog so that nothing gets cut off before it's fully fogged
+            let projection: Matrix4<f32> = perspective(
+                Deg(self.camera.fov),
+                self.screen_size.0 as f32 / self.screen_size.1 as f32,
+                0.1,
+                self.view_distance * 1.5,
+            );
 
             // camera/view transformation
             let view = self.camera.get_view_matrix();
+
+            let overdraw = self.debug.view == DebugView::Overdraw;
+            if !overdraw {
+                self.sky.draw(&projection, &view, self.world.time());
+            }
+            if self.debug.wireframe {
+                gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
+            }
+
+            gl::ActiveTexture(gl::TEXTURE0);
+            self.tex.bind();
+            self.program.bind();
+            self.program.set_mat4(c_str!("projection"), &projection);
             self.program.set_mat4(c_str!("view"), &view);
 
-            self.chunk.render();
+            let time = self.world.time();
+            let sun_direction = time.sun_direction();
+            self.program.set_vec3(
+                c_str!("sun_direction"),
+                &vec3(sun_direction.x, sun_direction.y, sun_direction.z),
+            );
+            self.program
+                .set_float(c_str!("sky_brightness"), time.sky_brightness());
+
+            let [r, g, b] = time.sky_colour();
+            self.program.set_vec3(c_str!("fog_colour"), &vec3(r, g, b));
+            self.program.set_int(
+                c_str!("fog_mode"),
+                match self.fog_mode {
+                    FogMode::Linear => 0,
+                    FogMode::Exponential => 1,
+                },
+            );
+            self.program
+                .set_float(c_str!("fog_start"), self.view_distance * 0.6);
+            self.program
+                .set_float(c_str!("fog_end"), self.view_distance);
+            self.program
+                .set_float(c_str!("ambient_strength"), self.ambient_strength);
+            self.program
+                .set_int(c_str!("debug_view"), self.debug.view.shader_id());
+
+            //every fragment adds a bit of colour, including the hidden ones
+            if overdraw {
+                gl::Disable(gl::DEPTH_TEST);
+                gl::Enable(gl::BLEND);
+                gl::BlendFunc(gl::ONE, gl::ONE);
+            }
+
+            self.program.set_float(c_str!("alpha_cutoff"), 0.0);
+            for chunk in self.chunks.values() {
+                self.draw_stats += chunk.render(RenderLayer::Opaque);
+            }
+            self.draw_entities();
+
+            self.program.set_float(c_str!("alpha_cutoff"), 0.5);
+            for chunk in self.chunks.values() {
+                self.draw_stats += chunk.render(RenderLayer::Cutout);
+            }
+
+            self.draw_translucent();
+            gl::Enable(gl::DEPTH_TEST);
+            gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL);
+
+            if self.debug.chunk_borders {
+                self.add_chunk_borders();
+                self.lines.draw(&projection, &view);
+            }
+            self.draw_hud();
+        }
+    }
+
+    //outlines of the chunk the camera is in and the ones around it, with a ring at the bottom
+    //and top of every section
+    fn add_chunk_borders(&mut self) {
+        let eye = self.eye();
+        let current = BlockPos::new(eye.x.floor() as i32, 0, eye.z.floor() as i32).chunk_pos();
+        let mut heights: Vec<f32> = (0..CHUNK_HEIGHT)
+            .step_by(SECTION_HEIGHT)
+            .map(|y| y as f32)
+            .collect();
+        heights.push(CHUNK_HEIGHT as f32);
+
+        for dx in -1..=1 {
+            for dz in -1..=1 {
+                let origin = ChunkPos::new(current.x + dx, current.z + dz).origin();
+                let colour = if dx == 0 && dz == 0 {
+                    [1.0, 1.0, 0.0]
+                } else {
+                    [1.0, 0.0, 0.0]
+                };
+                let (x0, z0) = (origin.x as f32, origin.z as f32);
+                let (x1, z1) = (x0 + CHUNK_WIDTH as f32, z0 + CHUNK_DEPTH as f32);
+                let corners = [[x0, z0], [x1, z0], [x1, z1], [x0, z1]];
+
+                for [x, z] in corners {
+                    self.lines
+                        .line([x, 0.0, z], [x, CHUNK_HEIGHT as f32, z], colour);
+                }
+                for &y in &heights {
+                    for i in 0..corners.len() {
+                        let [xa, za] = corners[i];
+                        let [xb, zb] = corners[(i + 1) % corners.len()];
+                        self.lines.line([xa, y, za], [xb, y, zb], colour);
+                    }
+                }
+            }
+        }
+    }
+
+    //a row of slots at the bottom of the screen showing the colour of the items in them and how
+    //many there are, with the name of the held item above it
+    fn draw_hotbar(&mut self, margin: f32) {
+        let (glyph_width, glyph_height) = self.text.glyph_size();
+        let slot_size = glyph_height * HOTBAR_SLOT_LINES;
+        let border = self.text.scale();
+        let width = slot_size * HOTBAR_SIZE as f32;
+        let left = ((self.screen_size.0 as f32 - width) * 0.5).floor();
+        let top = self.screen_size.1 as f32 - margin - slot_size;
+
+        let inventory = &self.player.inventory;
+        for (i, stack) in inventory.hotbar().iter().enumerate() {
+            let x = left + i as f32 * slot_size;
+            let background = if i == inventory.selected() {
+                [1.0, 1.0, 1.0, 0.8]
+            } else {
+                [0.0, 0.0, 0.0, 0.5]
+            };
+            self.text
+                .rect([x, top, x + slot_size, top + slot_size], background);
+            self.text.rect(
+                [
+                    x + border,
+                    top + border,
+                    x + slot_size - border,
+                    top + slot_size - border,
+                ],
+                [0.2, 0.2, 0.2, 0.8],
+            );
+
+            if let Some(stack) = stack {
+                let [r, g, b, _] = stack.item.colour();
+                let inset = slot_size * 0.25;
+                self.text.rect(
+                    [
+                        x + inset,
+                        top + inset,
+                        x + slot_size - inset,
+                        top + slot_size - inset,
+                    ],
+                    [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0],
+                );
+                if stack.count > 1 {
+                    let count = stack.count.to_string();
+                    let count_width = glyph_width * count.len() as f32;
+                    self.text.text(
+                        &count,
+                        x + slot_size - border * 2.0 - count_width,
+                        top + slot_size - border * 2.0 - glyph_height,
+                        [1.0, 1.0, 1.0, 1.0],
+                    );
+                }
+            }
+        }
+
+        if let Some(stack) = inventory.selected_stack() {
+            let name = stack.item.name().replace('_', " ");
+            let name_width = glyph_width * name.len() as f32;
+            self.text.text(
+                &name,
+                ((self.screen_size.0 as f32 - name_width) * 0.5).floor(),
+                top - margin - glyph_height,
+                [1.0, 1.0, 1.0, 1.0],
+            );
+        }
+    }
+
+    unsafe fn draw_hud(&mut self) {
+        let eye = self.eye();
+        let pos = BlockPos::new(
+            eye.x.floor() as i32,
+            eye.y.floor() as i32,
+            eye.z.floor() as i32,
+        );
+        let margin = HUD_MARGIN * self.text.scale();
+        let white = [1.0, 1.0, 1.0, 1.0];
+        let text = if self.show_debug_overlay {
+            self.debug_overlay(pos)
+        } else {
+            self.world.biome(pos).name().to_string()
+        };
+        self.text.text(&text, margin, margin, white);
+
+        self.draw_hotbar(margin);
+        self.draw_console(margin);
+        self.text.draw(self.screen_size.0, self.screen_size.1);
+    }
+
+    //what's shown in the top left corner with f3
+    fn debug_overlay(&self, pos: BlockPos) -> String {
+        let (average, longest) = self.frame_timer.frame_times();
+        let eye = self.eye();
+        let chunk = pos.chunk_pos();
+        let (local_x, local_y, local_z) = pos.local();
+
+        let mut lines = vec![
+            format!(
+                "{:.0} fps ({:.1} ms, longest {:.1} ms)",
+                self.frame_timer.fps(),
+                average,
+                longest
+            ),
+            format!("position: {:.2} {:.2} {:.2}", eye.x, eye.y, eye.z),
+            format!(
+                "facing: yaw {:.1} pitch {:.1}",
+                self.camera.yaw, self.camera.pitch
+            ),
+            format!("block: {} {} {}", pos.x, pos.y, pos.z),
+            format!(
+                "chunk: {} {} (in chunk {} {} {})",
+                chunk.x, chunk.z, local_x, local_y, local_z
+            ),
+            format!("biome: {}", self.world.biome(pos).name()),
+            format!(
+                "chunks: {} loaded, {} meshed",
+                self.world.loaded_chunks().count(),
+                self.chunks.len()
+            ),
+            format!(
+                "drawn: {} vertices, {} triangles",
+                self.draw_stats.vertices, self.draw_stats.triangles
+            ),
+            format!("time: {}", self.world.time().ticks()),
+        ];
+
+        match self.world.raycast(&eye, &self.look_direction(), REACH) {
+            Some(hit) => {
+                let face = match hit.face {
+                    Some(face) => format!("{:?}", face).to_lowercase(),
+                    None => "inside".to_string(),
+                };
+                lines.push(format!(
+                    "target: {} at {} {} {} ({} face)",
+                    self.world.block(hit.pos).name(),
+                    hit.pos.x,
+                    hit.pos.y,
+                    hit.pos.z,
+                    face
+                ));
+                if let Some(face) = hit.face {
+                    let front = hit.pos.neighbour(face);
+                    lines.push(format!(
+                        "light in front: sky {} block {}",
+                        self.world.sky_light(front),
+                        self.world.block_light(front)
+                    ));
+                }
+            }
+            None => lines.push("target: none".to_string()),
+        }
+        lines.push(format!(
+            "view: {} wireframe: {} borders: {} (F4-F6)",
+            self.debug.view.name(),
+            self.debug.wireframe,
+            self.debug.chunk_borders
+        ));
+        lines.join("\n")
+    }
+
+    //the console's output in the bottom left corner, with the line being typed below it
+    unsafe fn draw_console(&mut self, margin: f32) {
+        let open = self.console.is_open();
+        let shown = self
+            .console_shown_until
+            .is_some_and(|until| Instant::now() < until);
+        if !open && !shown {
+            return;
+        }
+
+        let (screen_width, screen_height) = (self.screen_size.0 as f32, self.screen_size.1 as f32);
+        let (_, glyph_height) = self.text.glyph_size();
+        //the font lays out text in its own unscaled pixels
+        let wrap_width = ((screen_width - margin * 2.0) / self.text.scale()) as u32;
+        let font = self.text.font();
+        let mut lines: Vec<String> = self
+            .console
+            .output()
+            .iter()
+            .flat_map(|line| font.wrap(line, wrap_width))
+            .collect();
+        lines.drain(..lines.len().saturating_sub(CONSOLE_LINES));
+        if let Some(input) = self.console.input() {
+            lines.push(format!("{}_", input));
+        }
+
+        let top = screen_height - margin - lines.len() as f32 * glyph_height;
+        if open {
+            self.text.rect(
+                [0.0, top - margin, screen_width, screen_height],
+                [0.0, 0.0, 0.0, 0.5],
+            );
         }
+        let white = [1.0, 1.0, 1.0, 1.0];
+        self.text.text(&lines.join("\n"), margin, top, white);
     }
+
+    unsafe fn draw_entities(&mut self) {
+        let mut mesh = Mesh::new();
+        for falling_block in self.world.falling_blocks() {
+            let position = &falling_block.position;
+            //lit by whatever the block is currently falling through
+            let pos = BlockPos::new(
+                position.x.floor() as i32,
+                (position.y + 0.5).floor() as i32,
+                position.z.floor() as i32,
+            );
+            let light = [
+                self.world.sky_light(pos) as f32 / MAX_LIGHT as f32,
+                self.world.block_light(pos) as f32 / MAX_LIGHT as f32,
+            ];
+            mesh.add_block(falling_block.block, position, light);
+        }
+
+        self.entities.upload(&mesh);
+        self.draw_stats += self.entities.render();
+    }
+
+    //blended faces have to be drawn from the furthest to the closest, both between chunks and
+    //inside of every chunk
+    unsafe fn draw_translucent(&mut self) {
+        let eye = self.eye();
+        let chunk_distance = |pos: &ChunkPos| {
+            let centre = pos.origin();
+            let dx = centre.x as f32 + CHUNK_WIDTH as f32 * 0.5 - eye.x;
+            let dz = centre.z as f32 + CHUNK_DEPTH as f32 * 0.5 - eye.z;
+            dx * dx + dz * dz
+        };
+        let mut chunks: Vec<(&ChunkPos, &mut Chunk)> = self.chunks.iter_mut().collect();
+        chunks.sort_by(|a, b| chunk_distance(b.0).total_cmp(&chunk_distance(a.0)));
+
+        self.program.set_float(c_str!("alpha_cutoff"), 0.0);
+        gl::Enable(gl::BLEND);
+        if self.debug.view == DebugView::Overdraw {
+            gl::BlendFunc(gl::ONE, gl::ONE);
+        } else {
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+        }
+        //translucent faces don't hide each other and are visible from both sides
+        gl::DepthMask(gl::FALSE);
+        gl::Disable(gl::CULL_FACE);
+
+        for (_, chunk) in chunks {
+            chunk.sort_translucent(&eye);
+            self.draw_stats += chunk.render(RenderLayer::Translucent);
+        }
+
+        gl::Enable(gl::CULL_FACE);
+        gl::DepthMask(gl::TRUE);
+        gl::Disable(gl::BLEND);
+    }
+}
+
+//the hotbar slot selected by a number key
+fn hotbar_slot(key: glfw::Key) -> Option<usize> {
+    let slot = match key {
+        glfw::Key::Num1 => 0,
+        glfw::Key::Num2 => 1,
+        glfw::Key::Num3 => 2,
+        glfw::Key::Num4 => 3,
+        glfw::Key::Num5 => 4,
+        glfw::Key::Num6 => 5,
+        glfw::Key::Num7 => 6,
+        glfw::Key::Num8 => 7,
+        glfw::Key::Num9 => 8,
+        _ => return None,
+    };
+    Some(slot)
 }
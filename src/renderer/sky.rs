@@ -0,0 +1,87 @@
+use crate::renderer::buffer::Buffer;
+use crate::renderer::program::ShaderProgram;
+use crate::renderer::shader::{Shader, ShaderError};
+use crate::renderer::vertex_array::VertexArray;
+use crate::renderer::vertex_array::VertexArrayConfiguration;
+use crate::world::time::WorldTime;
+
+use std::ffi::CStr;
+
+use cgmath::{vec3, Matrix3, Matrix4};
+
+#[rustfmt::skip]
+const CUBE_VERTICES: [f32; 108] = [
+    -1.0,  1.0, -1.0,  -1.0, -1.0, -1.0,   1.0, -1.0, -1.0,
+     1.0, -1.0, -1.0,   1.0,  1.0, -1.0,  -1.0,  1.0, -1.0,
+
+    -1.0, -1.0,  1.0,  -1.0, -1.0, -1.0,  -1.0,  1.0, -1.0,
+    -1.0,  1.0, -1.0,  -1.0,  1.0,  1.0,  -1.0, -1.0,  1.0,
+
+     1.0, -1.0, -1.0,   1.0, -1.0,  1.0,   1.0,  1.0,  1.0,
+     1.0,  1.0,  1.0,   1.0,  1.0, -1.0,   1.0, -1.0, -1.0,
+
+    -1.0, -1.0,  1.0,  -1.0,  1.0,  1.0,   1.0,  1.0,  1.0,
+     1.0,  1.0,  1.0,   1.0, -1.0,  1.0,  -1.0, -1.0,  1.0,
+
+    -1.0,  1.0, -1.0,   1.0,  1.0, -1.0,   1.0,  1.0,  1.0,
+     1.0,  1.0,  1.0,  -1.0,  1.0,  1.0,  -1.0,  1.0, -1.0,
+
+    -1.0, -1.0, -1.0,  -1.0, -1.0,  1.0,   1.0, -1.0, -1.0,
+     1.0, -1.0, -1.0,  -1.0, -1.0,  1.0,   1.0, -1.0,  1.0,
+];
+
+//procedural sky drawn on a cube around the camera before anything else
+pub struct Sky {
+    program: ShaderProgram,
+    vao: VertexArray,
+    _vbo: Buffer,
+}
+
+impl Sky {
+    pub unsafe fn new() -> Result<Self, ShaderError> {
+        let vertex_shader = Shader::new("src/shaders/sky.vs", gl::VERTEX_SHADER)?;
+        let fragment_shader = Shader::new("src/shaders/sky.frag", gl::FRAGMENT_SHADER)?;
+        let program = ShaderProgram::new(&[vertex_shader, fragment_shader])?;
+
+        let vao = VertexArray::new();
+        let vbo = Buffer::new(gl::ARRAY_BUFFER);
+        vao.bind();
+        vbo.set_data(&CUBE_VERTICES, gl::STATIC_DRAW);
+        vao.setup_vao(VertexArrayConfiguration::Xyz);
+
+        Ok(Self {
+            program,
+            vao,
+            _vbo: vbo,
+        })
+    }
+
+    pub unsafe fn draw(&self, projection: &Matrix4<f32>, view: &Matrix4<f32>, time: &WorldTime) {
+        //dropping the translation keeps the camera in the middle of the cube
+        let rotation = Matrix4::from(Matrix3::from_cols(
+            view.x.truncate(),
+            view.y.truncate(),
+            view.z.truncate(),
+        ));
+
+        self.program.bind();
+        self.program.set_mat4(c_str!("projection"), projection);
+        self.program.set_mat4(c_str!("view"), &rotation);
+
+        let [r, g, b] = time.sky_colour();
+        self.program.set_vec3(c_str!("sky_colour"), &vec3(r, g, b));
+        let sun_direction = time.sun_direction();
+        self.program.set_vec3(
+            c_str!("sun_direction"),
+            &vec3(sun_direction.x, sun_direction.y, sun_direction.z),
+        );
+
+        //the sky is seen from the inside and is always behind everything else
+        gl::Disable(gl::DEPTH_TEST);
+        gl::Disable(gl::CULL_FACE);
+        self.vao.bind();
+        gl::DrawArrays(gl::TRIANGLES, 0, (CUBE_VERTICES.len() / 3) as i32);
+        gl::Enable(gl::CULL_FACE);
+        gl::Enable(gl::DEPTH_TEST);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::renderer::buffer::Buffer;
+use crate::renderer::font::Font;
+use crate::renderer::program::ShaderProgram;
+use crate::renderer::shader::{Shader, ShaderError};
+use crate::renderer::texture::Texture;
+use crate::renderer::vertex_array::{VertexArray, VertexArrayConfiguration};
+
+use std::ffi::CStr;
+use std::path::Path;
+
+use cgmath::ortho;
+
+const FONT_PATH: &str = "src/resources/font_8x13.png";
+const GLYPH_WIDTH: u32 = 8;
+const GLYPH_HEIGHT: u32 = 13;
+
+//text is drawn with a dark copy of it one pixel down and to the right so it stays readable over
+//bright blocks
+const SHADOW_COLOUR: [f32; 4] = [0.0, 0.0, 0.0, 0.6];
+
+//2d text and rectangles on top of everything else, collected during the frame and drawn with a
+//single draw call. positions are in pixels from the top left corner of the screen
+pub struct TextRenderer {
+    program: ShaderProgram,
+    font: Font,
+    texture: Texture,
+    vao: VertexArray,
+    vbo: Buffer,
+    ibo: Buffer,
+
+    vertices: Vec<f32>,
+    indices: Vec<u32>,
+    //how many screen pixels a pixel of the font takes up
+    scale: f32,
+}
+
+impl TextRenderer {
+    pub unsafe fn new() -> Result<Self, ShaderError> {
+        let vertex_shader = Shader::new("src/shaders/text.vs", gl::VERTEX_SHADER)?;
+        let fragment_shader = Shader::new("src/shaders/text.frag", gl::FRAGMENT_SHADER)?;
+        let program = ShaderProgram::new(&[vertex_shader, fragment_shader])?;
+        program.set_int(c_str!("font"), 0);
+
+        let font = Font::load(Path::new(FONT_PATH), GLYPH_WIDTH, GLYPH_HEIGHT)
+            .expect("Cannot load the font");
+        let texture = Texture::from_image(font.image(), gl::NEAREST);
+
+        Ok(Self {
+            program,
+            font,
+            texture,
+            vao: VertexArray::new(),
+            vbo: Buffer::new(gl::ARRAY_BUFFER),
+            ibo: Buffer::new(gl::ELEMENT_ARRAY_BUFFER),
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            scale: 1.0,
+        })
+    }
+
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+
+    //the font only gets scaled by whole numbers so that its pixels stay sharp
+    pub fn set_scale(&mut self, scale: u32) {
+        self.scale = scale.max(1) as f32;
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    //size of a character on the screen
+    pub fn glyph_size(&self) -> (f32, f32) {
+        let (width, height) = self.font.glyph_size();
+        (width as f32 * self.scale, height as f32 * self.scale)
+    }
+
+    fn add_quad(&mut self, position: [f32; 4], uv: [f32; 4], colour: [f32; 4]) {
+        let [left, top, right, bottom] = position;
+        let [u0, v0, u1, v1] = uv;
+        let first = (self.vertices.len() / 8) as u32;
+        for (x, y, u, v) in [
+            (left, top, u0, v0),
+            (left, bottom, u0, v1),
+            (right, bottom, u1, v1),
+            (right, top, u1, v0),
+        ] {
+            self.vertices.extend_from_slice(&[x, y, u, v]);
+            self.vertices.extend_from_slice(&colour);
+        }
+        self.indices
+            .extend([0, 1, 2, 2, 3, 0].map(|index| first + index));
+    }
+
+    pub fn rect(&mut self, position: [f32; 4], colour: [f32; 4]) {
+        self.add_quad(position, self.font.solid_uv(), colour);
+    }
+
+    //lines are split on `\n`
+    pub fn text(&mut self, text: &str, x: f32, y: f32, colour: [f32; 4]) {
+        let shadow = self
+            .font
+            .layout(text, x + self.scale, y + self.scale, self.scale);
+        let glyphs = self.font.layout(text, x, y, self.scale);
+        for glyph in shadow {
+            self.add_quad(glyph.position, glyph.uv, SHADOW_COLOUR);
+        }
+        for glyph in glyphs {
+            self.add_quad(glyph.position, glyph.uv, colour);
+        }
+    }
+
+    //draws everything added since the last time and forgets it
+    pub unsafe fn draw(&mut self, screen_width: u32, screen_height: u32) {
+        if self.indices.is_empty() {
+            return;
+        }
+
+        let projection = ortho(
+            0.0,
+            screen_width as f32,
+            screen_height as f32,
+            0.0,
+            -1.0,
+            1.0,
+        );
+        self.program.bind();
+        self.program.set_mat4(c_str!("projection"), &projection);
+        gl::ActiveTexture(gl::TEXTURE0);
+        self.texture.bind();
+
+        self.vao.bind();
+        self.vbo.set_data(&self.vertices, gl::STREAM_DRAW);
+        self.ibo.set_data(&self.indices, gl::STREAM_DRAW);
+        self.vao
+            .setup_vao(VertexArrayConfiguration::XyTexCoordsAndRgba);
+
+        gl::Disable(gl::DEPTH_TEST);
+        gl::Disable(gl::CULL_FACE);
+        gl::Enable(gl::BLEND);
+        gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+        gl::DrawElements(
+            gl::TRIANGLES,
+            self.indices.len() as i32,
+            gl::UNSIGNED_INT,
+            std::ptr::null(),
+        );
+        gl::Disable(gl::BLEND);
+        gl::Enable(gl::CULL_FACE);
+        gl::Enable(gl::DEPTH_TEST);
+
+        self.vertices.clear();
+        self.indices.clear();
+    }
+}
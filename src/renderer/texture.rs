@@ -1,7 +1,5 @@
-use std::path::Path;
-
 extern crate image;
-use image::EncodableLayout;
+use image::{EncodableLayout, RgbaImage};
 
 pub struct Texture {
     tex_id: u32,
@@ -16,16 +14,16 @@ impl Drop for Texture {
 }
 
 impl Texture {
-    pub unsafe fn new(filepath: &Path) -> Self {
+    //`filter` is used for both minifying and magnifying, pixel art should use gl::NEAREST
+    pub unsafe fn from_image(img: &RgbaImage, filter: gl::types::GLenum) -> Self {
         let mut id: u32 = 0;
         gl::GenTextures(1, &mut id);
         gl::BindTexture(gl::TEXTURE_2D, id);
         gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
         gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, filter as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, filter as i32);
 
-        let img = image::open(filepath).unwrap().into_rgba8();
         gl::TexImage2D(
             gl::TEXTURE_2D,
             0,
@@ -3,8 +3,12 @@ use std::os::raw::c_void;
 
 #[derive(PartialEq, Eq)]
 pub enum VertexArrayConfiguration {
+    Xyz,
     XyzAndTexCoords,
+    XyzTexCoordsNormalAndLight,
     XyAndColour,
+    XyTexCoordsAndRgba,
+    XyzAndColour,
 }
 
 pub struct VertexArray {
@@ -32,7 +36,18 @@ impl VertexArray {
 
     pub fn setup_vao(&self, configuration: VertexArrayConfiguration) {
         unsafe {
-            if configuration == VertexArrayConfiguration::XyzAndTexCoords {
+            if configuration == VertexArrayConfiguration::Xyz {
+                self.bind();
+                gl::VertexAttribPointer(
+                    0,
+                    3,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (3 * std::mem::size_of::<GLfloat>()) as GLint,
+                    std::ptr::null::<c_void>(),
+                );
+                gl::EnableVertexAttribArray(0);
+            } else if configuration == VertexArrayConfiguration::XyzAndTexCoords {
                 self.bind();
                 gl::VertexAttribPointer(
                     0,
@@ -53,6 +68,47 @@ impl VertexArray {
                     (3 * std::mem::size_of::<GLfloat>()) as *const c_void,
                 );
                 gl::EnableVertexAttribArray(1);
+            } else if configuration == VertexArrayConfiguration::XyzTexCoordsNormalAndLight {
+                self.bind();
+                gl::VertexAttribPointer(
+                    0,
+                    3,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (10 * std::mem::size_of::<GLfloat>()) as GLint,
+                    std::ptr::null::<c_void>(),
+                );
+                gl::EnableVertexAttribArray(0);
+
+                gl::VertexAttribPointer(
+                    1,
+                    2,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (10 * std::mem::size_of::<GLfloat>()) as GLint,
+                    (3 * std::mem::size_of::<GLfloat>()) as *const c_void,
+                );
+                gl::EnableVertexAttribArray(1);
+
+                gl::VertexAttribPointer(
+                    2,
+                    3,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (10 * std::mem::size_of::<GLfloat>()) as GLint,
+                    (5 * std::mem::size_of::<GLfloat>()) as *const c_void,
+                );
+                gl::EnableVertexAttribArray(2);
+
+                gl::VertexAttribPointer(
+                    3,
+                    2,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (10 * std::mem::size_of::<GLfloat>()) as GLint,
+                    (8 * std::mem::size_of::<GLfloat>()) as *const c_void,
+                );
+                gl::EnableVertexAttribArray(3);
             } else if configuration == VertexArrayConfiguration::XyAndColour {
                 self.bind();
                 gl::VertexAttribPointer(
@@ -74,6 +130,58 @@ impl VertexArray {
                     (2 * std::mem::size_of::<GLfloat>()) as *const c_void,
                 );
                 gl::EnableVertexAttribArray(1);
+            } else if configuration == VertexArrayConfiguration::XyTexCoordsAndRgba {
+                self.bind();
+                gl::VertexAttribPointer(
+                    0,
+                    2,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (8 * std::mem::size_of::<GLfloat>()) as GLint,
+                    std::ptr::null::<c_void>(),
+                );
+                gl::EnableVertexAttribArray(0);
+
+                gl::VertexAttribPointer(
+                    1,
+                    2,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (8 * std::mem::size_of::<GLfloat>()) as GLint,
+                    (2 * std::mem::size_of::<GLfloat>()) as *const c_void,
+                );
+                gl::EnableVertexAttribArray(1);
+
+                gl::VertexAttribPointer(
+                    2,
+                    4,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (8 * std::mem::size_of::<GLfloat>()) as GLint,
+                    (4 * std::mem::size_of::<GLfloat>()) as *const c_void,
+                );
+                gl::EnableVertexAttribArray(2);
+            } else if configuration == VertexArrayConfiguration::XyzAndColour {
+                self.bind();
+                gl::VertexAttribPointer(
+                    0,
+                    3,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (6 * std::mem::size_of::<GLfloat>()) as GLint,
+                    std::ptr::null::<c_void>(),
+                );
+                gl::EnableVertexAttribArray(0);
+
+                gl::VertexAttribPointer(
+                    1,
+                    3,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (6 * std::mem::size_of::<GLfloat>()) as GLint,
+                    (3 * std::mem::size_of::<GLfloat>()) as *const c_void,
+                );
+                gl::EnableVertexAttribArray(1);
             }
         }
     }
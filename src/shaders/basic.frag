@@ -2,9 +2,69 @@
 out vec4 FragColor;
 
 in vec2 f_TexCoords;
+in vec3 f_Normal;
+//x is sky light, y is block light, both from 0.0 to 1.0
+in vec2 f_Light;
+//distance from the camera
+in float f_Distance;
 
 uniform sampler2D tex0;
+//fragments less opaque than this are thrown away, used for leaves and glass
+uniform float alpha_cutoff;
+//direction the sunlight travels in (from the sun towards the ground)
+uniform vec3 sun_direction;
+uniform float ambient_strength;
+//dims sky light at night, block light isn't affected
+uniform float sky_brightness;
+
+//0 is linear fog, 1 is exponential
+uniform int fog_mode;
+uniform vec3 fog_colour;
+uniform float fog_start;
+uniform float fog_end;
+
+//0 draws the world normally, 1 shows the normals, 2 the light levels with block light in red and
+//sky light in blue and 3 adds up a bit of colour for every fragment drawn to show overdraw
+uniform int debug_view;
+
+//0.0 means no fog, 1.0 means only the fog colour is visible
+float fog_amount() {
+    if (fog_mode == 0) {
+        return clamp((f_Distance - fog_start) / (fog_end - fog_start), 0.0, 1.0);
+    }
+    //reaches ~98% at fog_end
+    float density = 2.0 / fog_end;
+    return 1.0 - exp(-pow(f_Distance * density, 2.0));
+}
+
+//every level of light below the maximum makes the block 20% darker
+float brightness(float level) {
+    return pow(0.8, (1.0 - level) * 15.0);
+}
 
 void main() {
-    FragColor = texture(tex0, f_TexCoords);
+    vec4 colour = texture(tex0, f_TexCoords);
+    if (colour.a < alpha_cutoff) {
+        discard;
+    }
+    if (debug_view == 1) {
+        FragColor = vec4(normalize(f_Normal) * 0.5 + 0.5, 1.0);
+        return;
+    }
+    if (debug_view == 2) {
+        FragColor = vec4(f_Light.y, f_Light.y * 0.5 + f_Light.x * 0.3, f_Light.x, 1.0);
+        return;
+    }
+    if (debug_view == 3) {
+        FragColor = vec4(0.12, 0.06, 0.02, 1.0);
+        return;
+    }
+    float diffuse = max(dot(normalize(f_Normal), -sun_direction), 0.0);
+    float sun = ambient_strength + (1.0 - ambient_strength) * diffuse;
+
+    float sky = brightness(f_Light.x) * sun * sky_brightness;
+    float block = brightness(f_Light.y);
+    float light = max(sky, block);
+    vec3 lit = colour.rgb * light;
+    FragColor = vec4(mix(lit, fog_colour, fog_amount()), colour.a);
 }
\ No newline at end of file
@@ -1,8 +1,13 @@
 #version 330
 layout (location=0) in vec3 a_Pos;
 layout (location=1) in vec2 a_TexCoords;
+layout (location=2) in vec3 a_Normal;
+layout (location=3) in vec2 a_Light;
 
 out vec2 f_TexCoords;
+out vec3 f_Normal;
+out vec2 f_Light;
+out float f_Distance;
 
 uniform mat4 model;
 uniform mat4 view;
@@ -10,5 +15,9 @@ uniform mat4 projection;
 
 void main() {
     f_TexCoords = a_TexCoords;
-    gl_Position = projection * view * model * vec4(a_Pos, 1.0);
+    f_Normal = mat3(model) * a_Normal;
+    f_Light = a_Light;
+    vec4 view_position = view * model * vec4(a_Pos, 1.0);
+    f_Distance = length(view_position.xyz);
+    gl_Position = projection * view_position;
 }
@@ -0,0 +1,8 @@
+#version 330
+out vec4 FragColor;
+
+in vec3 f_Colour;
+
+void main() {
+    FragColor = vec4(f_Colour, 1.0);
+}
@@ -0,0 +1,13 @@
+#version 330
+layout (location=0) in vec3 a_Pos;
+layout (location=1) in vec3 a_Colour;
+
+out vec3 f_Colour;
+
+uniform mat4 view;
+uniform mat4 projection;
+
+void main() {
+    f_Colour = a_Colour;
+    gl_Position = projection * view * vec4(a_Pos, 1.0);
+}
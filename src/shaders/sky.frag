@@ -0,0 +1,33 @@
+#version 330
+out vec4 FragColor;
+
+in vec3 f_Direction;
+
+//colour at the horizon, the same one the fog fades into
+uniform vec3 sky_colour;
+//direction the sunlight travels in, the sun itself is on the opposite side
+uniform vec3 sun_direction;
+
+const float SUN_SIZE = 0.9985;
+const float MOON_SIZE = 0.9992;
+const vec3 SUN_COLOUR = vec3(1.0, 0.95, 0.8);
+const vec3 MOON_COLOUR = vec3(0.8, 0.82, 0.9);
+
+//1.0 inside of the disc with a soft edge
+float disc(vec3 direction, vec3 centre, float size) {
+    return smoothstep(size, size + 0.0004, dot(direction, centre));
+}
+
+void main() {
+    vec3 direction = normalize(f_Direction);
+    vec3 sun = -normalize(sun_direction);
+
+    //deeper blue towards the top of the sky
+    vec3 zenith = sky_colour * vec3(0.55, 0.7, 0.95);
+    vec3 colour = mix(sky_colour, zenith, pow(max(direction.y, 0.0), 0.6));
+
+    colour = mix(colour, SUN_COLOUR, disc(direction, sun, SUN_SIZE));
+    colour = mix(colour, MOON_COLOUR, disc(direction, -sun, MOON_SIZE));
+
+    FragColor = vec4(colour, 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+#version 330
+layout (location=0) in vec3 a_Pos;
+
+out vec3 f_Direction;
+
+//only the rotation of the camera, the sky never gets any closer
+uniform mat4 view;
+uniform mat4 projection;
+
+void main() {
+    f_Direction = a_Pos;
+    gl_Position = projection * view * vec4(a_Pos, 1.0);
+}
@@ -0,0 +1,12 @@
+#version 330
+out vec4 FragColor;
+
+in vec2 f_TexCoords;
+in vec4 f_Colour;
+
+//white glyphs, the colour comes from the vertices
+uniform sampler2D font;
+
+void main() {
+    FragColor = f_Colour * texture(font, f_TexCoords);
+}
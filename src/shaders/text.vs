@@ -0,0 +1,16 @@
+#version 330
+layout (location=0) in vec2 a_Pos;
+layout (location=1) in vec2 a_TexCoords;
+layout (location=2) in vec4 a_Colour;
+
+out vec2 f_TexCoords;
+out vec4 f_Colour;
+
+//maps pixels with y going down to the screen
+uniform mat4 projection;
+
+void main() {
+    f_TexCoords = a_TexCoords;
+    f_Colour = a_Colour;
+    gl_Position = projection * vec4(a_Pos, 0.0, 1.0);
+}
@@ -1 +1,3 @@
 pub mod math;
+pub mod nbt;
+pub mod random;
@@ -0,0 +1,361 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+
+use thiserror::Error;
+
+//minecraft's named binary tag format, everything is big endian and strings are prefixed with
+//their length in bytes
+#[derive(Debug, Error)]
+pub enum NbtError {
+    #[error{"{0}"}]
+    IoError(#[from] io::Error),
+    #[error("Unknown tag type {0}")]
+    UnknownTag(u8),
+    #[error("The root tag has to be a compound")]
+    InvalidRoot,
+    #[error("Negative length {0}")]
+    NegativeLength(i32),
+    #[error("Tags in a list have to be of the same type")]
+    MixedList,
+    #[error("Strings can be at most 65535 bytes long, not {0}")]
+    StringTooLong(usize),
+    #[error("Lists and compounds are nested more than {MAX_DEPTH} deep")]
+    TooDeep,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<Tag>),
+    //sorted by name so that writing the same data always gives the same bytes
+    Compound(BTreeMap<String, Tag>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+const END: u8 = 0;
+//how deep lists and compounds can be nested when reading, the same as minecraft. files can come
+//from anywhere and reading them recurses, so without this a small file could overflow the stack
+const MAX_DEPTH: usize = 512;
+
+impl Tag {
+    fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => 1,
+            Tag::Short(_) => 2,
+            Tag::Int(_) => 3,
+            Tag::Long(_) => 4,
+            Tag::Float(_) => 5,
+            Tag::Double(_) => 6,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(_) => 9,
+            Tag::Compound(_) => 10,
+            Tag::IntArray(_) => 11,
+            Tag::LongArray(_) => 12,
+        }
+    }
+
+    //the tag with the name if this is a compound which has one
+    pub fn get(&self, name: &str) -> Option<&Tag> {
+        match self {
+            Tag::Compound(tags) => tags.get(name),
+            _ => None,
+        }
+    }
+
+    //any of the integer tags
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Tag::Byte(value) => Some(*value as i64),
+            Tag::Short(value) => Some(*value as i64),
+            Tag::Int(value) => Some(*value as i64),
+            Tag::Long(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+fn read_u8(reader: &mut impl Read) -> io::Result<u8> {
+    let mut bytes = [0; 1];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_length(reader: &mut impl Read) -> Result<usize, NbtError> {
+    let length = i32::from_be_bytes(read_bytes(reader)?);
+    usize::try_from(length).map_err(|_| NbtError::NegativeLength(length))
+}
+
+fn read_string(reader: &mut impl Read) -> Result<String, NbtError> {
+    let length = u16::from_be_bytes(read_bytes(reader)?) as usize;
+    let mut bytes = vec![0; length];
+    reader.read_exact(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+//`depth` is how many lists and compounds the tag is in
+fn read_payload(reader: &mut impl Read, id: u8, depth: usize) -> Result<Tag, NbtError> {
+    if depth > MAX_DEPTH {
+        return Err(NbtError::TooDeep);
+    }
+    Ok(match id {
+        1 => Tag::Byte(i8::from_be_bytes(read_bytes(reader)?)),
+        2 => Tag::Short(i16::from_be_bytes(read_bytes(reader)?)),
+        3 => Tag::Int(i32::from_be_bytes(read_bytes(reader)?)),
+        4 => Tag::Long(i64::from_be_bytes(read_bytes(reader)?)),
+        5 => Tag::Float(f32::from_be_bytes(read_bytes(reader)?)),
+        6 => Tag::Double(f64::from_be_bytes(read_bytes(reader)?)),
+        7 => {
+            //the length isn't trusted with allocating everything up front
+            let length = read_length(reader)?;
+            let mut bytes = Vec::new();
+            reader.take(length as u64).read_to_end(&mut bytes)?;
+            if bytes.len() != length {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+            Tag::ByteArray(bytes.into_iter().map(|byte| byte as i8).collect())
+        }
+        8 => Tag::String(read_string(reader)?),
+        9 => {
+            let element_id = read_u8(reader)?;
+            let length = read_length(reader)?;
+            let mut tags = Vec::with_capacity(length.min(1024));
+            for _ in 0..length {
+                tags.push(read_payload(reader, element_id, depth + 1)?);
+            }
+            Tag::List(tags)
+        }
+        10 => {
+            let mut tags = BTreeMap::new();
+            loop {
+                let id = read_u8(reader)?;
+                if id == END {
+                    break;
+                }
+                let name = read_string(reader)?;
+                tags.insert(name, read_payload(reader, id, depth + 1)?);
+            }
+            Tag::Compound(tags)
+        }
+        11 => {
+            let length = read_length(reader)?;
+            let mut values = Vec::with_capacity(length.min(1024));
+            for _ in 0..length {
+                values.push(i32::from_be_bytes(read_bytes(reader)?));
+            }
+            Tag::IntArray(values)
+        }
+        12 => {
+            let length = read_length(reader)?;
+            let mut values = Vec::with_capacity(length.min(1024));
+            for _ in 0..length {
+                values.push(i64::from_be_bytes(read_bytes(reader)?));
+            }
+            Tag::LongArray(values)
+        }
+        id => return Err(NbtError::UnknownTag(id)),
+    })
+}
+
+//reads the root compound and its name, the data has to be decompressed already
+pub fn read(reader: &mut impl Read) -> Result<(String, Tag), NbtError> {
+    if read_u8(reader)? != Tag::Compound(BTreeMap::new()).id() {
+        return Err(NbtError::InvalidRoot);
+    }
+    let name = read_string(reader)?;
+    Ok((name, read_payload(reader, 10, 0)?))
+}
+
+fn write_string(writer: &mut impl Write, value: &str) -> Result<(), NbtError> {
+    let length = u16::try_from(value.len()).map_err(|_| NbtError::StringTooLong(value.len()))?;
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(value.as_bytes())?;
+    Ok(())
+}
+
+fn write_payload(writer: &mut impl Write, tag: &Tag) -> Result<(), NbtError> {
+    match tag {
+        Tag::Byte(value) => writer.write_all(&value.to_be_bytes())?,
+        Tag::Short(value) => writer.write_all(&value.to_be_bytes())?,
+        Tag::Int(value) => writer.write_all(&value.to_be_bytes())?,
+        Tag::Long(value) => writer.write_all(&value.to_be_bytes())?,
+        Tag::Float(value) => writer.write_all(&value.to_be_bytes())?,
+        Tag::Double(value) => writer.write_all(&value.to_be_bytes())?,
+        Tag::ByteArray(values) => {
+            writer.write_all(&(values.len() as i32).to_be_bytes())?;
+            let bytes: Vec<u8> = values.iter().map(|value| *value as u8).collect();
+            writer.write_all(&bytes)?;
+        }
+        Tag::String(value) => write_string(writer, value)?,
+        Tag::List(tags) => {
+            //empty lists are written as lists of end tags
+            let element_id = tags.first().map_or(END, Tag::id);
+            if tags.iter().any(|tag| tag.id() != element_id) {
+                return Err(NbtError::MixedList);
+            }
+            writer.write_all(&[element_id])?;
+            writer.write_all(&(tags.len() as i32).to_be_bytes())?;
+            for tag in tags {
+                write_payload(writer, tag)?;
+            }
+        }
+        Tag::Compound(tags) => {
+            for (name, tag) in tags {
+                writer.write_all(&[tag.id()])?;
+                write_string(writer, name)?;
+                write_payload(writer, tag)?;
+            }
+            writer.write_all(&[END])?;
+        }
+        Tag::IntArray(values) => {
+            writer.write_all(&(values.len() as i32).to_be_bytes())?;
+            for value in values {
+                writer.write_all(&value.to_be_bytes())?;
+            }
+        }
+        Tag::LongArray(values) => {
+            writer.write_all(&(values.len() as i32).to_be_bytes())?;
+            for value in values {
+                writer.write_all(&value.to_be_bytes())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn write(writer: &mut impl Write, name: &str, root: &Tag) -> Result<(), NbtError> {
+    if !matches!(root, Tag::Compound(_)) {
+        return Err(NbtError::InvalidRoot);
+    }
+    writer.write_all(&[root.id()])?;
+    write_string(writer, name)?;
+    write_payload(writer, root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(root: &Tag) -> Tag {
+        let mut bytes = Vec::new();
+        write(&mut bytes, "root", root).unwrap();
+        let (name, tag) = read(&mut bytes.as_slice()).unwrap();
+        assert_eq!(name, "root");
+        tag
+    }
+
+    #[test]
+    fn every_tag_survives_a_round_trip() {
+        let tags = [
+            ("byte", Tag::Byte(-7)),
+            ("short", Tag::Short(-1234)),
+            ("int", Tag::Int(123_456_789)),
+            ("long", Tag::Long(-9_876_543_210)),
+            ("float", Tag::Float(1.5)),
+            ("double", Tag::Double(-0.25)),
+            ("byte_array", Tag::ByteArray(vec![-128, 0, 127])),
+            ("string", Tag::String("minecraft:stone".to_string())),
+            ("list", Tag::List(vec![Tag::Int(1), Tag::Int(2)])),
+            ("empty_list", Tag::List(Vec::new())),
+            (
+                "compound",
+                Tag::Compound(BTreeMap::from([
+                    (
+                        "nested".to_string(),
+                        Tag::List(vec![Tag::String(String::new())]),
+                    ),
+                    ("empty".to_string(), Tag::Compound(BTreeMap::new())),
+                ])),
+            ),
+            ("int_array", Tag::IntArray(vec![i32::MIN, 0, i32::MAX])),
+            ("long_array", Tag::LongArray(vec![i64::MIN, 0, i64::MAX])),
+        ];
+        let root = Tag::Compound(
+            tags.into_iter()
+                .map(|(name, tag)| (name.to_string(), tag))
+                .collect(),
+        );
+        assert_eq!(round_trip(&root), root);
+    }
+
+    #[test]
+    fn invalid_tags_are_not_written() {
+        let mut bytes = Vec::new();
+        assert!(matches!(
+            write(&mut bytes, "", &Tag::Int(1)),
+            Err(NbtError::InvalidRoot)
+        ));
+
+        let mixed = Tag::Compound(BTreeMap::from([(
+            "list".to_string(),
+            Tag::List(vec![Tag::Int(1), Tag::Long(1)]),
+        )]));
+        assert!(matches!(
+            write(&mut bytes, "", &mixed),
+            Err(NbtError::MixedList)
+        ));
+
+        let long = Tag::Compound(BTreeMap::from([(
+            "string".to_string(),
+            Tag::String("a".repeat(65536)),
+        )]));
+        assert!(matches!(
+            write(&mut bytes, "", &long),
+            Err(NbtError::StringTooLong(65536))
+        ));
+    }
+
+    #[test]
+    fn deeply_nested_tags_are_rejected() {
+        let mut root = Tag::List(Vec::new());
+        for _ in 0..MAX_DEPTH - 1 {
+            root = Tag::List(vec![root]);
+        }
+        let root = Tag::Compound(BTreeMap::from([("list".to_string(), root)]));
+        assert_eq!(round_trip(&root), root);
+
+        //a compound which is its own first tag, too deep to ever end
+        let mut bytes = vec![10, 0, 0];
+        for _ in 0..100_000 {
+            bytes.extend_from_slice(&[10, 0, 0]);
+        }
+        assert!(matches!(
+            read(&mut bytes.as_slice()),
+            Err(NbtError::TooDeep)
+        ));
+    }
+
+    #[test]
+    fn byte_arrays_longer_than_the_data_are_truncated() {
+        let mut bytes = vec![10, 0, 0, 7, 0, 1, b'a'];
+        bytes.extend_from_slice(&i32::MAX.to_be_bytes());
+        bytes.extend_from_slice(&[1, 2, 3]);
+        match read(&mut bytes.as_slice()) {
+            Err(NbtError::IoError(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof),
+            result => panic!("expected an early end, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn the_longest_strings_fit() {
+        let root = Tag::Compound(BTreeMap::from([(
+            "string".to_string(),
+            Tag::String("a".repeat(65535)),
+        )]));
+        assert_eq!(round_trip(&root), root);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::utils::random::Random;
+use crate::world::block::Block;
+use crate::world::entity;
+use crate::world::fluid;
+use crate::world::position::{BlockPos, Direction};
+use crate::world::World;
+
+//light needed above grass for it to spread and for crops to grow
+const MIN_GROWTH_LIGHT: u8 = 9;
+//leaves further away than this from any log decay
+const LEAVES_REACH: i32 = 4;
+pub const MAX_CROP_AGE: u8 = 7;
+//ticks between losing support and starting to fall
+const FALL_DELAY: u64 = 2;
+
+fn light_above(world: &World, pos: BlockPos) -> u8 {
+    let above = pos.neighbour(Direction::Up);
+    world.sky_light(above).max(world.block_light(above))
+}
+
+//grass dies when covered and otherwise slowly creeps onto nearby dirt
+fn grass_tick(world: &mut World, pos: BlockPos, random: &mut Random) {
+    let above = world.block(pos.neighbour(Direction::Up));
+    if above.is_opaque() || above.fluid().is_some() {
+        world.set_block(pos, Block::Dirt);
+        return;
+    }
+    if light_above(world, pos) < MIN_GROWTH_LIGHT {
+        return;
+    }
+
+    let target = pos.offset(random.range(-1, 1), random.range(-3, 1), random.range(-1, 1));
+    let target_above = world.block(target.neighbour(Direction::Up));
+    if world.block(target) == Block::Dirt
+        && !target_above.is_opaque()
+        && target_above.fluid().is_none()
+        && light_above(world, target) >= MIN_GROWTH_LIGHT
+    {
+        world.set_block(target, Block::Grass);
+    }
+}
+
+fn crop_tick(world: &mut World, pos: BlockPos, age: u8, random: &mut Random) {
+    if age < MAX_CROP_AGE
+        && light_above(world, pos) >= MIN_GROWTH_LIGHT
+        && random.next_below(3) == 0
+    {
+        world.set_block(pos, Block::Wheat(age + 1));
+    }
+}
+
+fn leaves_tick(world: &mut World, pos: BlockPos) {
+    for dy in -LEAVES_REACH..=LEAVES_REACH {
+        for dz in -LEAVES_REACH..=LEAVES_REACH {
+            for dx in -LEAVES_REACH..=LEAVES_REACH {
+                if world.block(pos.offset(dx, dy, dz)) == Block::Log {
+                    return;
+                }
+            }
+        }
+    }
+    world.set_block(pos, Block::Air);
+}
+
+impl Block {
+    //whether the block does anything when picked for a random tick
+    pub fn has_random_ticks(&self) -> bool {
+        matches!(self, Block::Grass | Block::Wheat(_) | Block::Leaves)
+    }
+
+    //called whenever the block itself or one of its six neighbours changed
+    pub fn on_neighbour_update(&self, world: &mut World, pos: BlockPos) {
+        if let Some((fluid, _)) = self.fluid() {
+            world.schedule_tick(pos, fluid.tick_rate());
+        } else if self.has_gravity() {
+            world.schedule_tick(pos, FALL_DELAY);
+        }
+    }
+
+    //called for updates queued with `World::schedule_tick`
+    pub fn on_scheduled_tick(&self, world: &mut World, pos: BlockPos) {
+        if self.fluid().is_some() {
+            fluid::update(world, pos);
+        } else if self.has_gravity()
+            && world.block(pos.neighbour(Direction::Down)).is_replaceable()
+            && pos.y > 0
+        {
+            entity::start_falling(world, pos);
+        }
+    }
+
+    //called for blocks picked at random every tick, used for slow changes like plants growing
+    pub fn on_random_tick(&self, world: &mut World, pos: BlockPos, random: &mut Random) {
+        match self {
+            Block::Grass => grass_tick(world, pos, random),
+            Block::Wheat(age) => crop_tick(world, pos, *age, random),
+            Block::Leaves => leaves_tick(world, pos),
+            _ => (),
+        }
+    }
+}
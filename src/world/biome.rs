@@ -0,0 +1,89 @@
+use crate::world::block::Block;
+use crate::world::decoration::Feature;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Biome {
+    Ocean,
+    Plains,
+    Desert,
+    Forest,
+    Mountains,
+}
+
+impl Biome {
+    //picks the biome from the climate at a column, both values are roughly in -1..1
+    pub fn from_climate(temperature: f64, humidity: f64) -> Self {
+        if humidity > 0.45 {
+            Biome::Ocean
+        } else if temperature < -0.35 {
+            Biome::Mountains
+        } else if temperature > 0.3 && humidity < 0.0 {
+            Biome::Desert
+        } else if humidity > 0.1 {
+            Biome::Forest
+        } else {
+            Biome::Plains
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Biome::Ocean => "ocean",
+            Biome::Plains => "plains",
+            Biome::Desert => "desert",
+            Biome::Forest => "forest",
+            Biome::Mountains => "mountains",
+        }
+    }
+
+    //average height of the terrain, in blocks
+    pub fn base_height(&self) -> f64 {
+        match self {
+            Biome::Ocean => 12.0,
+            Biome::Plains => 21.0,
+            Biome::Desert => 21.0,
+            Biome::Forest => 22.0,
+            Biome::Mountains => 28.0,
+        }
+    }
+
+    //how far the terrain goes above and below the base height
+    pub fn height_variation(&self) -> f64 {
+        match self {
+            Biome::Ocean => 3.0,
+            Biome::Plains => 3.0,
+            Biome::Desert => 4.0,
+            Biome::Forest => 6.0,
+            Biome::Mountains => 16.0,
+        }
+    }
+
+    //the topmost block of a column
+    pub fn surface_block(&self) -> Block {
+        match self {
+            Biome::Ocean | Biome::Desert => Block::Sand,
+            Biome::Plains | Biome::Forest => Block::Grass,
+            Biome::Mountains => Block::Stone,
+        }
+    }
+
+    //the few blocks between the surface and the stone
+    pub fn filler_block(&self) -> Block {
+        match self {
+            Biome::Ocean | Biome::Desert => Block::Sand,
+            Biome::Plains | Biome::Forest => Block::Dirt,
+            Biome::Mountains => Block::Stone,
+        }
+    }
+
+    //features scattered over the biome's chunks and how many times per chunk each is tried
+    pub fn decorations(&self) -> &'static [(Feature, u32)] {
+        match self {
+            Biome::Ocean => &[],
+            Biome::Plains => &[(Feature::Tree, 1), (Feature::Flower, 6)],
+            Biome::Desert => &[(Feature::Boulder, 1)],
+            Biome::Forest => &[(Feature::Tree, 8), (Feature::Flower, 2)],
+            Biome::Mountains => &[(Feature::Boulder, 3), (Feature::Tree, 1)],
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::mem;
+
+use crate::world::fluid::{Fluid, SOURCE};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Block {
+    Air,
+    Stone,
+    Dirt,
+    Grass,
+    Torch,
+    Glass,
+    Leaves,
+    //fluids carry their level, see `world::fluid`
+    Water(u8),
+    StainedGlass,
+    Lava(u8),
+    Obsidian,
+    Cobblestone,
+    Log,
+    //age of the crop, fully grown at `behaviour::MAX_CROP_AGE`
+    Wheat(u8),
+    Sand,
+    Gravel,
+    Flower,
+    CoalOre,
+    IronOre,
+    GoldOre,
+    DiamondOre,
+    //crafting next to one uses a 3x3 grid instead of the 2x2 one of the inventory
+    CraftingTable,
+}
+
+//which pass of the renderer the block is drawn in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RenderLayer {
+    Opaque,
+    //fully see-through where the texture's alpha is low, drawn without blending
+    Cutout,
+    //blended with whatever is behind it, drawn last and back to front
+    Translucent,
+}
+
+impl Block {
+    //every kind of block, the order decides where the block's texture ends up in the atlas. the
+    //index is also the block's id in save files, so blocks may only be appended, never reordered
+    //or removed
+    pub const ALL: [Block; 22] = [
+        Block::Air,
+        Block::Stone,
+        Block::Dirt,
+        Block::Grass,
+        Block::Torch,
+        Block::Glass,
+        Block::Leaves,
+        Block::Water(SOURCE),
+        Block::StainedGlass,
+        Block::Lava(SOURCE),
+        Block::Obsidian,
+        Block::Cobblestone,
+        Block::Log,
+        Block::Wheat(0),
+        Block::Sand,
+        Block::Gravel,
+        Block::Flower,
+        Block::CoalOre,
+        Block::IronOre,
+        Block::GoldOre,
+        Block::DiamondOre,
+        Block::CraftingTable,
+    ];
+
+    //used in config files and commands, lowercase with underscores
+    pub fn name(&self) -> &'static str {
+        match self {
+            Block::Air => "air",
+            Block::Stone => "stone",
+            Block::Dirt => "dirt",
+            Block::Grass => "grass",
+            Block::Torch => "torch",
+            Block::Glass => "glass",
+            Block::Leaves => "leaves",
+            Block::Water(_) => "water",
+            Block::StainedGlass => "stained_glass",
+            Block::Lava(_) => "lava",
+            Block::Obsidian => "obsidian",
+            Block::Cobblestone => "cobblestone",
+            Block::Log => "log",
+            Block::Wheat(_) => "wheat",
+            Block::Sand => "sand",
+            Block::Gravel => "gravel",
+            Block::Flower => "flower",
+            Block::CoalOre => "coal_ore",
+            Block::IronOre => "iron_ore",
+            Block::GoldOre => "gold_ore",
+            Block::DiamondOre => "diamond_ore",
+            Block::CraftingTable => "crafting_table",
+        }
+    }
+
+    //blocks with data get the same default as in `ALL`
+    pub fn from_name(name: &str) -> Option<Block> {
+        Block::ALL.into_iter().find(|block| block.name() == name)
+    }
+
+    pub fn render_layer(&self) -> RenderLayer {
+        match self {
+            Block::Torch | Block::Glass | Block::Leaves | Block::Wheat(_) | Block::Flower => {
+                RenderLayer::Cutout
+            }
+            Block::Water(_) | Block::StainedGlass => RenderLayer::Translucent,
+            _ => RenderLayer::Opaque,
+        }
+    }
+
+    //whether the block fully hides its neighbours and stops light
+    pub fn is_opaque(&self) -> bool {
+        *self != Block::Air && self.fluid().is_none() && self.render_layer() == RenderLayer::Opaque
+    }
+
+    //whether the player can point at the block to break it or place against it
+    pub fn is_targetable(&self) -> bool {
+        *self != Block::Air && self.fluid().is_none()
+    }
+
+    //whether another block can simply take this one's place, like sand landing on it
+    pub fn is_replaceable(&self) -> bool {
+        matches!(self, Block::Air | Block::Torch | Block::Flower) || self.fluid().is_some()
+    }
+
+    //falls down when there's nothing underneath it
+    pub fn has_gravity(&self) -> bool {
+        matches!(self, Block::Sand | Block::Gravel)
+    }
+
+    pub fn fluid(&self) -> Option<(Fluid, u8)> {
+        match self {
+            Block::Water(level) => Some((Fluid::Water, *level)),
+            Block::Lava(level) => Some((Fluid::Lava, *level)),
+            _ => None,
+        }
+    }
+
+    //same kind of block, ignoring data like the level of a fluid
+    pub fn is_same_kind(&self, other: &Block) -> bool {
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+
+    //whether the block can be held in the inventory, even if breaking it drops nothing
+    pub fn is_item(&self) -> bool {
+        *self != Block::Air && self.fluid().is_none()
+    }
+
+    //the item the player gets for breaking the block in survival
+    pub fn drop(&self) -> Option<Block> {
+        match self {
+            Block::Air | Block::Water(_) | Block::Lava(_) => None,
+            Block::Glass | Block::StainedGlass | Block::Leaves => None,
+            Block::Stone => Some(Block::Cobblestone),
+            Block::Grass => Some(Block::Dirt),
+            Block::Wheat(_) => Some(Block::Wheat(0)),
+            _ => Some(*self),
+        }
+    }
+
+    pub fn light_emission(&self) -> u8 {
+        match self {
+            Block::Lava(_) => 15,
+            Block::Torch => 14,
+            _ => 0,
+        }
+    }
+
+    pub fn is_ore(&self) -> bool {
+        matches!(
+            self,
+            Block::CoalOre | Block::IronOre | Block::GoldOre | Block::DiamondOre
+        )
+    }
+
+    //base colour of the block, used to generate its texture
+    pub fn colour(&self) -> [u8; 4] {
+        match self {
+            Block::Air => [0, 0, 0, 0],
+            Block::Stone => [125, 125, 125, 255],
+            Block::Dirt => [134, 96, 67, 255],
+            Block::Grass => [95, 159, 53, 255],
+            Block::Torch => [255, 200, 80, 255],
+            Block::Glass => [200, 230, 240, 255],
+            Block::Leaves => [60, 120, 40, 255],
+            Block::Water(_) => [40, 80, 200, 160],
+            Block::StainedGlass => [180, 40, 40, 140],
+            Block::Lava(_) => [207, 92, 15, 255],
+            Block::Obsidian => [25, 20, 38, 255],
+            Block::Cobblestone => [110, 110, 110, 255],
+            Block::Log => [102, 81, 51, 255],
+            Block::Wheat(_) => [190, 170, 70, 255],
+            Block::Sand => [219, 207, 163, 255],
+            Block::Gravel => [136, 126, 126, 255],
+            Block::Flower => [200, 40, 40, 255],
+            Block::CoalOre => [40, 40, 40, 255],
+            Block::IronOre => [216, 175, 147, 255],
+            Block::GoldOre => [250, 220, 60, 255],
+            Block::DiamondOre => [90, 220, 225, 255],
+            Block::CraftingTable => [150, 110, 65, 255],
+        }
+    }
+
+    //position of the block's texture in the atlas
+    pub fn texture_index(&self) -> usize {
+        self.id() as usize
+    }
+
+    //number of the kind of block in save files, new blocks have to go at the end of `ALL` to keep
+    //old saves working
+    pub fn id(&self) -> u8 {
+        Block::ALL
+            .iter()
+            .position(|block| block.is_same_kind(self))
+            .unwrap() as u8
+    }
+
+    //state saved next to the id, like the level of a fluid
+    pub fn data(&self) -> u8 {
+        match self {
+            Block::Water(data) | Block::Lava(data) | Block::Wheat(data) => *data,
+            _ => 0,
+        }
+    }
+
+    pub fn from_id(id: u8, data: u8) -> Option<Block> {
+        Some(match *Block::ALL.get(id as usize)? {
+            Block::Water(_) => Block::Water(data),
+            Block::Lava(_) => Block::Lava(data),
+            Block::Wheat(_) => Block::Wheat(data),
+            block => block,
+        })
+    }
+}
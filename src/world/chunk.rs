@@ -0,0 +1,67 @@
+use crate::world::block::Block;
+
+pub const CHUNK_WIDTH: usize = 16;
+pub const CHUNK_DEPTH: usize = 16;
+pub const CHUNK_HEIGHT: usize = 50;
+//chunks are split into sections this tall for random ticks
+pub const SECTION_HEIGHT: usize = 16;
+
+pub const MAX_LIGHT: u8 = 15;
+
+//block and light storage of a single chunk, all coordinates are local to the chunk
+pub struct ChunkData {
+    blocks: Vec<Block>,
+    sky_light: Vec<u8>,
+    block_light: Vec<u8>,
+}
+
+impl ChunkData {
+    pub fn new() -> Self {
+        let volume = CHUNK_WIDTH * CHUNK_HEIGHT * CHUNK_DEPTH;
+        Self {
+            blocks: vec![Block::Air; volume],
+            sky_light: vec![0; volume],
+            block_light: vec![0; volume],
+        }
+    }
+
+    fn index(x: usize, y: usize, z: usize) -> usize {
+        (y * CHUNK_DEPTH + z) * CHUNK_WIDTH + x
+    }
+
+    pub fn block(&self, x: usize, y: usize, z: usize) -> Block {
+        self.blocks[Self::index(x, y, z)]
+    }
+
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, block: Block) {
+        self.blocks[Self::index(x, y, z)] = block;
+    }
+
+    pub fn sky_light(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.sky_light[Self::index(x, y, z)]
+    }
+
+    pub fn set_sky_light(&mut self, x: usize, y: usize, z: usize, level: u8) {
+        self.sky_light[Self::index(x, y, z)] = level;
+    }
+
+    pub fn block_light(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.block_light[Self::index(x, y, z)]
+    }
+
+    pub fn set_block_light(&mut self, x: usize, y: usize, z: usize, level: u8) {
+        self.block_light[Self::index(x, y, z)] = level;
+    }
+
+    //darkens the whole chunk so its light can be computed again from scratch
+    pub fn clear_light(&mut self) {
+        self.sky_light.fill(0);
+        self.block_light.fill(0);
+    }
+}
+
+impl Default for ChunkData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
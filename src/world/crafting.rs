@@ -0,0 +1,656 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::world::block::Block;
+use crate::world::inventory::{Inventory, ItemStack};
+
+//width and height of the crafting grid in the inventory and of the one of a crafting table
+pub const INVENTORY_GRID_SIZE: usize = 2;
+pub const TABLE_GRID_SIZE: usize = 3;
+
+//written in place of an ingredient for cells of a shaped recipe which have to stay empty
+const EMPTY_CELL: &str = "_";
+
+#[derive(Debug, Error)]
+pub enum RecipeError {
+    #[error{"{0}"}]
+    IoError(#[from] io::Error),
+    #[error("Line {0} is not in the `shaped|shapeless <output> = <ingredients>` format")]
+    InvalidLine(usize),
+    #[error("Unknown block `{1}` on line {0}")]
+    UnknownBlock(usize, String),
+    #[error("Invalid count `{1}` on line {0}")]
+    InvalidCount(usize, String),
+    #[error("The ingredients on line {0} don't fit in a 3x3 grid")]
+    InvalidPattern(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Ingredients {
+    //have to be laid out like this but can be anywhere in the grid. the rows go from the top and
+    //the pattern is trimmed so that its outer rows and columns aren't empty
+    Shaped {
+        width: usize,
+        height: usize,
+        cells: Vec<Option<Block>>,
+    },
+    //in any order and anywhere in the grid
+    Shapeless(Vec<Block>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recipe {
+    pub ingredients: Ingredients,
+    pub output: ItemStack,
+}
+
+impl Recipe {
+    //`rows` go from the top and have to be the same length
+    pub fn shaped(rows: &[Vec<Option<Block>>], output: ItemStack) -> Self {
+        let filled = |x: usize, y: usize| rows[y][x].is_some();
+        let width = rows.first().map_or(0, Vec::len);
+        let columns: Vec<usize> = (0..width)
+            .filter(|&x| (0..rows.len()).any(|y| filled(x, y)))
+            .collect();
+        let lines: Vec<usize> = (0..rows.len())
+            .filter(|&y| (0..width).any(|x| filled(x, y)))
+            .collect();
+
+        let (mut width, mut height, mut cells) = (0, 0, Vec::new());
+        if let (Some(&left), Some(&right), Some(&top), Some(&bottom)) =
+            (columns.first(), columns.last(), lines.first(), lines.last())
+        {
+            width = right - left + 1;
+            height = bottom - top + 1;
+            for row in &rows[top..=bottom] {
+                cells.extend_from_slice(&row[left..=right]);
+            }
+        }
+
+        Self {
+            ingredients: Ingredients::Shaped {
+                width,
+                height,
+                cells,
+            },
+            output,
+        }
+    }
+
+    pub fn shapeless(ingredients: Vec<Block>, output: ItemStack) -> Self {
+        Self {
+            ingredients: Ingredients::Shapeless(ingredients),
+            output,
+        }
+    }
+
+    //whether the recipe can be made in a grid of the size at all
+    pub fn fits(&self, grid_size: usize) -> bool {
+        match &self.ingredients {
+            Ingredients::Shaped { width, height, .. } => {
+                *width <= grid_size && *height <= grid_size
+            }
+            Ingredients::Shapeless(ingredients) => ingredients.len() <= grid_size * grid_size,
+        }
+    }
+
+    //one of every item the recipe takes
+    pub fn items(&self) -> Vec<Block> {
+        match &self.ingredients {
+            Ingredients::Shaped { cells, .. } => cells.iter().flatten().copied().collect(),
+            Ingredients::Shapeless(ingredients) => ingredients.clone(),
+        }
+    }
+
+    pub fn matches(&self, grid: &CraftingGrid) -> bool {
+        let items = grid.items();
+        match &self.ingredients {
+            Ingredients::Shaped {
+                width,
+                height,
+                cells,
+            } => {
+                //the smallest rectangle around the items in the grid has to be the pattern
+                let Some((left, top, right, bottom)) = grid.bounds() else {
+                    return false;
+                };
+                if right - left + 1 != *width || bottom - top + 1 != *height {
+                    return false;
+                }
+                (0..*height).all(|y| {
+                    (0..*width)
+                        .all(|x| items[(top + y) * grid.size() + left + x] == cells[y * width + x])
+                })
+            }
+            Ingredients::Shapeless(ingredients) => {
+                let mut left: Vec<Block> = items.into_iter().flatten().collect();
+                if left.len() != ingredients.len() {
+                    return false;
+                }
+                for ingredient in ingredients {
+                    match left.iter().position(|item| item == ingredient) {
+                        Some(index) => {
+                            left.swap_remove(index);
+                        }
+                        None => return false,
+                    }
+                }
+                true
+            }
+        }
+    }
+}
+
+//every recipe there is, the first one matching the grid is used
+pub struct RecipeBook {
+    recipes: Vec<Recipe>,
+}
+
+impl RecipeBook {
+    pub fn new() -> Self {
+        Self {
+            recipes: Vec::new(),
+        }
+    }
+
+    pub fn load(filepath: &Path) -> Result<Self, RecipeError> {
+        let src = fs::read_to_string(filepath)?;
+        Self::parse(&src)
+    }
+
+    //one recipe per line, `#` starts a comment:
+    //`shaped 4*torch = coal_ore / log` has the rows of the pattern separated by `/` and the cells
+    //of a row by spaces, with `_` for cells that have to stay empty
+    //`shapeless grass = dirt leaves` lists the ingredients in any order
+    //the count in front of the output is optional
+    pub fn parse(src: &str) -> Result<Self, RecipeError> {
+        let mut book = Self::new();
+        for (line_number, line) in src.lines().enumerate() {
+            let line_number = line_number + 1;
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (head, ingredients) = line
+                .split_once('=')
+                .ok_or(RecipeError::InvalidLine(line_number))?;
+            let (kind, output) = head
+                .trim()
+                .split_once(' ')
+                .ok_or(RecipeError::InvalidLine(line_number))?;
+            let output = parse_output(line_number, output.trim())?;
+            let recipe = match kind {
+                "shaped" => {
+                    let rows = ingredients
+                        .split('/')
+                        .map(|row| {
+                            row.split_whitespace()
+                                .map(|cell| parse_cell(line_number, cell))
+                                .collect::<Result<Vec<_>, _>>()
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let width = rows[0].len();
+                    if width == 0
+                        || width > TABLE_GRID_SIZE
+                        || rows.len() > TABLE_GRID_SIZE
+                        || rows.iter().any(|row| row.len() != width)
+                        || rows.iter().flatten().all(Option::is_none)
+                    {
+                        return Err(RecipeError::InvalidPattern(line_number));
+                    }
+                    Recipe::shaped(&rows, output)
+                }
+                "shapeless" => {
+                    let ingredients = ingredients
+                        .split_whitespace()
+                        .map(|name| parse_block(line_number, name))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if ingredients.is_empty() || ingredients.len() > TABLE_GRID_SIZE.pow(2) {
+                        return Err(RecipeError::InvalidPattern(line_number));
+                    }
+                    Recipe::shapeless(ingredients, output)
+                }
+                _ => return Err(RecipeError::InvalidLine(line_number)),
+            };
+            book.add(recipe);
+        }
+        Ok(book)
+    }
+
+    pub fn add(&mut self, recipe: Recipe) {
+        self.recipes.push(recipe);
+    }
+
+    pub fn recipes(&self) -> &[Recipe] {
+        &self.recipes
+    }
+
+    //the recipe for what's in the grid
+    pub fn find(&self, grid: &CraftingGrid) -> Option<&Recipe> {
+        self.recipes.iter().find(|recipe| recipe.matches(grid))
+    }
+
+    pub fn recipes_for(&self, item: Block) -> impl Iterator<Item = &Recipe> {
+        self.recipes
+            .iter()
+            .filter(move |recipe| recipe.output.item == item)
+    }
+}
+
+impl Default for RecipeBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_block(line_number: usize, name: &str) -> Result<Block, RecipeError> {
+    match Block::from_name(name) {
+        Some(Block::Air) | None => Err(RecipeError::UnknownBlock(line_number, name.to_string())),
+        Some(block) => Ok(block),
+    }
+}
+
+fn parse_cell(line_number: usize, cell: &str) -> Result<Option<Block>, RecipeError> {
+    if cell == EMPTY_CELL {
+        return Ok(None);
+    }
+    parse_block(line_number, cell).map(Some)
+}
+
+//a block name with an optional count in front, like `4*torch`
+fn parse_output(line_number: usize, output: &str) -> Result<ItemStack, RecipeError> {
+    let (count, name) = match output.split_once('*') {
+        Some((count, name)) => {
+            let count: u32 = count
+                .trim()
+                .parse()
+                .ok()
+                .filter(|&count| count > 0)
+                .ok_or_else(|| RecipeError::InvalidCount(line_number, count.to_string()))?;
+            (count, name.trim())
+        }
+        None => (1, output),
+    };
+    Ok(ItemStack::new(parse_block(line_number, name)?, count))
+}
+
+//a square of slots items are put in to craft with them
+pub struct CraftingGrid {
+    size: usize,
+    //row by row from the top left
+    slots: Vec<Option<ItemStack>>,
+}
+
+impl CraftingGrid {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            slots: vec![None; size * size],
+        }
+    }
+
+    //the one in the player's inventory
+    pub fn inventory() -> Self {
+        Self::new(INVENTORY_GRID_SIZE)
+    }
+
+    pub fn table() -> Self {
+        Self::new(TABLE_GRID_SIZE)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn slot(&self, x: usize, y: usize) -> Option<ItemStack> {
+        self.slots[y * self.size + x]
+    }
+
+    //stacks without any items empty the slot
+    pub fn set_slot(&mut self, x: usize, y: usize, stack: Option<ItemStack>) {
+        self.slots[y * self.size + x] = stack.filter(|stack| stack.count > 0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+
+    //the item in every slot, row by row from the top left
+    pub fn items(&self) -> Vec<Option<Block>> {
+        self.slots
+            .iter()
+            .map(|slot| slot.map(|stack| stack.item))
+            .collect()
+    }
+
+    //the smallest rectangle around every item in the grid as (left, top, right, bottom)
+    fn bounds(&self) -> Option<(usize, usize, usize, usize)> {
+        let filled = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.is_some())
+            .map(|(i, _)| (i % self.size, i / self.size));
+        filled.fold(None, |bounds, (x, y)| match bounds {
+            None => Some((x, y, x, y)),
+            Some((left, top, right, bottom)) => {
+                Some((left.min(x), top.min(y), right.max(x), bottom.max(y)))
+            }
+        })
+    }
+
+    //what crafting would make right now
+    pub fn result(&self, recipes: &RecipeBook) -> Option<ItemStack> {
+        recipes.find(self).map(|recipe| recipe.output)
+    }
+
+    //uses up one item from every slot for what the grid makes
+    pub fn craft(&mut self, recipes: &RecipeBook) -> Option<ItemStack> {
+        let output = self.result(recipes)?;
+        for slot in self.slots.iter_mut() {
+            if let Some(stack) = slot {
+                stack.count -= 1;
+                if stack.count == 0 {
+                    *slot = None;
+                }
+            }
+        }
+        Some(output)
+    }
+
+    //lays out one of every ingredient of the recipe taken from the inventory, in the top left
+    //corner for shaped recipes. nothing is taken unless the grid is empty, the recipe fits and
+    //the inventory has everything
+    pub fn place(&mut self, recipe: &Recipe, inventory: &mut Inventory) -> bool {
+        if !self.is_empty() || !recipe.fits(self.size) {
+            return false;
+        }
+        let items = recipe.items();
+        let mut needed: Vec<(Block, u32)> = Vec::new();
+        for item in &items {
+            match needed.iter_mut().find(|(needed, _)| needed == item) {
+                Some((_, count)) => *count += 1,
+                None => needed.push((*item, 1)),
+            }
+        }
+        if needed
+            .iter()
+            .any(|&(item, count)| inventory.count(item) < count)
+        {
+            return false;
+        }
+        for &(item, count) in &needed {
+            inventory.remove(item, count);
+        }
+
+        let one = |item: Block| Some(ItemStack::new(item, 1));
+        match &recipe.ingredients {
+            Ingredients::Shaped {
+                width,
+                height,
+                cells,
+            } => {
+                for y in 0..*height {
+                    for x in 0..*width {
+                        self.set_slot(x, y, cells[y * width + x].and_then(one));
+                    }
+                }
+            }
+            Ingredients::Shapeless(ingredients) => {
+                for (i, &item) in ingredients.iter().enumerate() {
+                    self.slots[i] = one(item);
+                }
+            }
+        }
+        true
+    }
+
+    //empties the grid, returning what was in it
+    pub fn take_all(&mut self) -> Vec<ItemStack> {
+        self.slots.iter_mut().filter_map(Option::take).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack(item: Block, count: u32) -> Option<ItemStack> {
+        Some(ItemStack::new(item, count))
+    }
+
+    fn error(src: &str) -> RecipeError {
+        match RecipeBook::parse(src) {
+            Ok(_) => panic!("`{}` should not parse", src),
+            Err(err) => err,
+        }
+    }
+
+    #[test]
+    fn parses_comments_counts_and_empty_cells() {
+        let book = RecipeBook::parse(
+            "# a comment\n\
+             \n\
+             shaped 4*torch = coal_ore / log # after a recipe\n\
+             shaped obsidian = _ stone / stone _\n\
+             shapeless grass = dirt leaves\n",
+        )
+        .unwrap();
+        let recipes = book.recipes();
+        assert_eq!(recipes.len(), 3);
+
+        assert_eq!(recipes[0].output, ItemStack::new(Block::Torch, 4));
+        assert_eq!(
+            recipes[0].ingredients,
+            Ingredients::Shaped {
+                width: 1,
+                height: 2,
+                cells: vec![Some(Block::CoalOre), Some(Block::Log)],
+            }
+        );
+        assert_eq!(recipes[1].output, ItemStack::new(Block::Obsidian, 1));
+        assert_eq!(
+            recipes[1].ingredients,
+            Ingredients::Shaped {
+                width: 2,
+                height: 2,
+                cells: vec![None, Some(Block::Stone), Some(Block::Stone), None],
+            }
+        );
+        assert_eq!(
+            recipes[2].ingredients,
+            Ingredients::Shapeless(vec![Block::Dirt, Block::Leaves])
+        );
+    }
+
+    #[test]
+    fn empty_outer_cells_are_trimmed() {
+        let book = RecipeBook::parse("shaped stone = _ _ _ / _ gravel _ / _ _ _").unwrap();
+        assert_eq!(
+            book.recipes()[0].ingredients,
+            Ingredients::Shaped {
+                width: 1,
+                height: 1,
+                cells: vec![Some(Block::Gravel)],
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_recipes_are_reported_with_their_line() {
+        assert!(matches!(
+            error("\nshaped torch"),
+            RecipeError::InvalidLine(2)
+        ));
+        assert!(matches!(error("shaped = log"), RecipeError::InvalidLine(1)));
+        assert!(matches!(
+            error("smelted glass = sand"),
+            RecipeError::InvalidLine(1)
+        ));
+
+        assert!(matches!(
+            error("shaped marble = log"),
+            RecipeError::UnknownBlock(1, name) if name == "marble"
+        ));
+        assert!(matches!(
+            error("shapeless torch = air"),
+            RecipeError::UnknownBlock(1, name) if name == "air"
+        ));
+
+        assert!(matches!(
+            error("shaped 0*torch = log"),
+            RecipeError::InvalidCount(1, count) if count == "0"
+        ));
+        assert!(matches!(
+            error("shaped many*torch = log"),
+            RecipeError::InvalidCount(1, _)
+        ));
+
+        for src in [
+            "shaped torch = log log / log",
+            "shaped torch = log log log log",
+            "shaped torch = log / log / log / log",
+            "shaped torch = _ _ / _ _",
+            "shaped torch =",
+            "shapeless torch =",
+            "shapeless torch = log log log log log log log log log log",
+        ] {
+            assert!(
+                matches!(error(src), RecipeError::InvalidPattern(1)),
+                "{}",
+                src
+            );
+        }
+
+        assert!(matches!(
+            RecipeBook::load(Path::new("src/resources/missing_recipes.txt")),
+            Err(RecipeError::IoError(_))
+        ));
+    }
+
+    #[test]
+    fn shaped_recipes_match_anywhere_in_the_grid() {
+        let book = RecipeBook::parse("shaped 4*torch = coal_ore / log").unwrap();
+        for top in 0..2 {
+            for left in 0..3 {
+                let mut grid = CraftingGrid::table();
+                grid.set_slot(left, top, stack(Block::CoalOre, 1));
+                grid.set_slot(left, top + 1, stack(Block::Log, 1));
+                assert_eq!(grid.result(&book), stack(Block::Torch, 4));
+
+                //the shape has to stay the same
+                grid.set_slot(left, top, None);
+                grid.set_slot((left + 1) % 3, top, stack(Block::CoalOre, 1));
+                assert_eq!(grid.result(&book), None);
+            }
+        }
+
+        //upside down
+        let mut grid = CraftingGrid::inventory();
+        grid.set_slot(0, 0, stack(Block::Log, 1));
+        grid.set_slot(0, 1, stack(Block::CoalOre, 1));
+        assert_eq!(grid.result(&book), None);
+    }
+
+    #[test]
+    fn big_recipes_need_a_crafting_table() {
+        let book =
+            RecipeBook::parse("shaped obsidian = _ stone _ / stone diamond_ore stone / _ stone _")
+                .unwrap();
+        let recipe = &book.recipes()[0];
+        assert!(recipe.fits(TABLE_GRID_SIZE));
+        assert!(!recipe.fits(INVENTORY_GRID_SIZE));
+
+        let mut inventory = Inventory::new();
+        inventory.add(Block::Stone, 4);
+        inventory.add(Block::DiamondOre, 1);
+        let mut grid = CraftingGrid::inventory();
+        assert!(!grid.place(recipe, &mut inventory));
+        assert!(grid.is_empty());
+        assert_eq!(inventory.count(Block::Stone), 4);
+
+        let mut grid = CraftingGrid::table();
+        assert!(grid.place(recipe, &mut inventory));
+        assert_eq!(grid.result(&book), stack(Block::Obsidian, 1));
+    }
+
+    #[test]
+    fn shapeless_recipes_match_in_any_order() {
+        let book = RecipeBook::parse("shapeless 2*gravel = cobblestone sand sand").unwrap();
+        let orders = [
+            [(0, 0), (1, 0), (0, 1)],
+            [(1, 1), (0, 0), (1, 0)],
+            [(0, 1), (1, 1), (1, 0)],
+        ];
+        for [cobblestone, sand, more_sand] in orders {
+            let mut grid = CraftingGrid::inventory();
+            grid.set_slot(cobblestone.0, cobblestone.1, stack(Block::Cobblestone, 1));
+            grid.set_slot(sand.0, sand.1, stack(Block::Sand, 1));
+            grid.set_slot(more_sand.0, more_sand.1, stack(Block::Sand, 1));
+            assert_eq!(grid.result(&book), stack(Block::Gravel, 2));
+        }
+
+        let mut grid = CraftingGrid::inventory();
+        grid.set_slot(0, 0, stack(Block::Cobblestone, 1));
+        grid.set_slot(1, 0, stack(Block::Sand, 1));
+        assert_eq!(grid.result(&book), None);
+        grid.set_slot(0, 1, stack(Block::Sand, 1));
+        grid.set_slot(1, 1, stack(Block::Dirt, 1));
+        assert_eq!(grid.result(&book), None);
+    }
+
+    #[test]
+    fn crafting_uses_one_item_from_every_slot() {
+        let book = RecipeBook::parse("shapeless grass = dirt leaves").unwrap();
+        let mut grid = CraftingGrid::inventory();
+        grid.set_slot(0, 0, stack(Block::Dirt, 2));
+        grid.set_slot(1, 1, stack(Block::Leaves, 1));
+
+        assert_eq!(grid.craft(&book), stack(Block::Grass, 1));
+        assert_eq!(grid.slot(0, 0), stack(Block::Dirt, 1));
+        assert_eq!(grid.slot(1, 1), None);
+        assert_eq!(grid.craft(&book), None);
+        assert_eq!(grid.take_all(), vec![ItemStack::new(Block::Dirt, 1)]);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn placing_takes_everything_or_nothing() {
+        let book = RecipeBook::parse("shaped crafting_table = log log / log log").unwrap();
+        let recipe = &book.recipes()[0];
+        let mut inventory = Inventory::new();
+        inventory.add(Block::Log, 3);
+
+        let mut grid = CraftingGrid::inventory();
+        assert!(!grid.place(recipe, &mut inventory));
+        assert!(grid.is_empty());
+        assert_eq!(inventory.count(Block::Log), 3);
+
+        inventory.add(Block::Log, 2);
+        assert!(grid.place(recipe, &mut inventory));
+        assert_eq!(inventory.count(Block::Log), 1);
+        assert_eq!(grid.result(&book), stack(Block::CraftingTable, 1));
+
+        //not on top of what's already there
+        inventory.add(Block::Log, 4);
+        assert!(!grid.place(recipe, &mut inventory));
+        assert_eq!(inventory.count(Block::Log), 5);
+    }
+
+    #[test]
+    fn loads_the_game_recipes() {
+        let book = RecipeBook::load(Path::new("src/resources/recipes.txt")).unwrap();
+        assert_eq!(book.recipes().len(), 9);
+        let torches: Vec<_> = book.recipes_for(Block::Torch).collect();
+        assert_eq!(torches.len(), 1);
+        assert_eq!(torches[0].output.count, 4);
+        assert!(book
+            .recipes()
+            .iter()
+            .all(|recipe| recipe.fits(TABLE_GRID_SIZE)));
+    }
+}
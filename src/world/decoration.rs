@@ -0,0 +1,87 @@
+use crate::utils::random::Random;
+use crate::world::block::Block;
+use crate::world::position::BlockPos;
+
+//small structures scattered over the terrain after it's generated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feature {
+    Tree,
+    Boulder,
+    Flower,
+}
+
+impl Feature {
+    //whether the feature can be placed on top of `ground`
+    pub fn can_stand_on(&self, ground: Block) -> bool {
+        match self {
+            Feature::Tree => matches!(ground, Block::Grass | Block::Dirt),
+            Feature::Boulder => ground.is_opaque(),
+            Feature::Flower => ground == Block::Grass,
+        }
+    }
+
+    //adds the blocks making up the feature, `base` is the first block above the ground
+    pub fn place(&self, random: &mut Random, base: BlockPos, blocks: &mut Vec<(BlockPos, Block)>) {
+        match self {
+            Feature::Tree => place_tree(random, base, blocks),
+            Feature::Boulder => place_boulder(random, base, blocks),
+            Feature::Flower => blocks.push((base, Block::Flower)),
+        }
+    }
+}
+
+//decorations never cut into the terrain, where they overlap each other the larger parts win, which
+//keeps the result the same whichever order the features are placed in
+pub fn can_replace(existing: Block, block: Block) -> bool {
+    let priority = |block: Block| match block {
+        Block::Air => 0,
+        Block::Flower => 1,
+        Block::Leaves => 2,
+        Block::Log => 3,
+        _ => 4,
+    };
+    priority(block) > priority(existing)
+}
+
+fn place_tree(random: &mut Random, base: BlockPos, blocks: &mut Vec<(BlockPos, Block)>) {
+    let top = base.y + random.range(4, 6) - 1;
+
+    //two wide layers of leaves around the top of the trunk and two narrow ones above them
+    for y in top - 2..=top + 1 {
+        let radius: i32 = if y < top { 2 } else { 1 };
+        for dz in -radius..=radius {
+            for dx in -radius..=radius {
+                //some of the corners are cut off so that the crown looks rounder
+                let corner = dx.abs() == radius && dz.abs() == radius;
+                if corner && (y == top + 1 || random.next_below(2) == 0) {
+                    continue;
+                }
+                blocks.push((BlockPos::new(base.x + dx, y, base.z + dz), Block::Leaves));
+            }
+        }
+    }
+
+    for y in base.y..=top {
+        blocks.push((BlockPos::new(base.x, y, base.z), Block::Log));
+    }
+}
+
+//a rough ball of stone centred on the ground, only the part above the ground ends up placed
+fn place_boulder(random: &mut Random, base: BlockPos, blocks: &mut Vec<(BlockPos, Block)>) {
+    let radius = random.range(1, 2);
+    for dy in -radius..=radius {
+        for dz in -radius..=radius {
+            for dx in -radius..=radius {
+                if dx * dx + dy * dy + dz * dz > radius * radius + 1 {
+                    continue;
+                }
+                let block = if random.next_below(3) == 0 {
+                    Block::Stone
+                } else {
+                    Block::Cobblestone
+                };
+                blocks.push((base.offset(dx, dy, dz), block));
+            }
+        }
+    }
+}
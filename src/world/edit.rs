@@ -0,0 +1,452 @@
+use thiserror::Error;
+
+use crate::world::block::Block;
+use crate::world::position::BlockPos;
+use crate::world::schematic::Schematic;
+use crate::world::World;
+
+//edits remembered for undo, the oldest ones are forgotten first
+const HISTORY_LENGTH: usize = 32;
+//the most blocks a single edit can change
+pub const MAX_VOLUME: usize = 1 << 18;
+
+#[derive(Debug, Error)]
+pub enum EditError {
+    #[error("Both corners of the selection have to be set first")]
+    NoSelection,
+    #[error("Nothing has been copied yet")]
+    EmptyClipboard,
+    #[error("The edit would change {0} blocks, at most {MAX_VOLUME} can be changed at once")]
+    TooBig(usize),
+    #[error(
+        "The selection has too many blocks to count, at most {MAX_VOLUME} can be changed at once"
+    )]
+    Uncountable,
+}
+
+//turns around the y axis, as seen from above
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rotation {
+    Clockwise,
+    Half,
+    CounterClockwise,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+//the box of blocks between two corners, both included
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Selection {
+    min: BlockPos,
+    max: BlockPos,
+}
+
+impl Selection {
+    pub fn new(a: BlockPos, b: BlockPos) -> Self {
+        Self {
+            min: BlockPos::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: BlockPos::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    pub fn min(&self) -> BlockPos {
+        self.min
+    }
+
+    pub fn max(&self) -> BlockPos {
+        self.max
+    }
+
+    //none if there are more blocks than a usize can count
+    pub fn volume(&self) -> Option<usize> {
+        let size = |min: i32, max: i32| usize::try_from(max as i64 - min as i64 + 1).ok();
+        size(self.min.x, self.max.x)?
+            .checked_mul(size(self.min.y, self.max.y)?)?
+            .checked_mul(size(self.min.z, self.max.z)?)
+    }
+
+    pub fn contains(&self, pos: BlockPos) -> bool {
+        (self.min.x..=self.max.x).contains(&pos.x)
+            && (self.min.y..=self.max.y).contains(&pos.y)
+            && (self.min.z..=self.max.z).contains(&pos.z)
+    }
+
+    //going through x first, then z and then y
+    pub fn positions(&self) -> impl Iterator<Item = BlockPos> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| {
+            (min.z..=max.z).flat_map(move |z| (min.x..=max.x).map(move |x| BlockPos::new(x, y, z)))
+        })
+    }
+}
+
+//the blocks an edit replaced and what it replaced them with
+struct Edit {
+    before: Vec<(BlockPos, Block)>,
+    after: Vec<(BlockPos, Block)>,
+}
+
+//world edit tools for building, every operation changing the world is placed in one batch and
+//can be undone
+pub struct Editor {
+    corners: [Option<BlockPos>; 2],
+    clipboard: Option<Schematic>,
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl Editor {
+    pub fn new() -> Self {
+        Self {
+            corners: [None, None],
+            clipboard: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    pub fn set_first_corner(&mut self, pos: BlockPos) {
+        self.corners[0] = Some(pos);
+    }
+
+    pub fn set_second_corner(&mut self, pos: BlockPos) {
+        self.corners[1] = Some(pos);
+    }
+
+    pub fn selection(&self) -> Option<Selection> {
+        match self.corners {
+            [Some(a), Some(b)] => Some(Selection::new(a, b)),
+            _ => None,
+        }
+    }
+
+    fn checked_selection(&self) -> Result<Selection, EditError> {
+        let selection = self.selection().ok_or(EditError::NoSelection)?;
+        match selection.volume() {
+            Some(volume) if volume <= MAX_VOLUME => Ok(selection),
+            Some(volume) => Err(EditError::TooBig(volume)),
+            None => Err(EditError::Uncountable),
+        }
+    }
+
+    pub fn clipboard(&self) -> Option<&Schematic> {
+        self.clipboard.as_ref()
+    }
+
+    //places the blocks and remembers the change, returning how many blocks changed
+    fn apply(&mut self, world: &mut World, blocks: Vec<(BlockPos, Block)>) -> usize {
+        let before = world.set_blocks(blocks);
+        if before.is_empty() {
+            return 0;
+        }
+        let after = before
+            .iter()
+            .map(|(pos, _)| (*pos, world.block(*pos)))
+            .collect();
+
+        let changed = before.len();
+        self.undo.push(Edit { before, after });
+        if self.undo.len() > HISTORY_LENGTH {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        changed
+    }
+
+    pub fn fill(&mut self, world: &mut World, block: Block) -> Result<usize, EditError> {
+        let selection = self.checked_selection()?;
+        let blocks = selection.positions().map(|pos| (pos, block)).collect();
+        Ok(self.apply(world, blocks))
+    }
+
+    //replaces every block of the same kind as `from`, whatever its data is
+    pub fn replace(
+        &mut self,
+        world: &mut World,
+        from: Block,
+        to: Block,
+    ) -> Result<usize, EditError> {
+        let selection = self.checked_selection()?;
+        let blocks = selection
+            .positions()
+            .filter(|pos| world.block(*pos).is_same_kind(&from))
+            .map(|pos| (pos, to))
+            .collect();
+        Ok(self.apply(world, blocks))
+    }
+
+    //copies the selection to the clipboard, returning how many blocks were copied
+    pub fn copy(&mut self, world: &World) -> Result<usize, EditError> {
+        let selection = self.checked_selection()?;
+        let clipboard = Schematic::copy(world, selection.min(), selection.max());
+        let (width, height, length) = clipboard.size();
+        self.clipboard = Some(clipboard);
+        Ok(width * height * length)
+    }
+
+    //places the clipboard with its lowest corner at `origin`
+    pub fn paste(&mut self, world: &mut World, origin: BlockPos) -> Result<usize, EditError> {
+        let clipboard = self.clipboard.as_ref().ok_or(EditError::EmptyClipboard)?;
+        let blocks = clipboard.blocks_at(origin).collect();
+        Ok(self.apply(world, blocks))
+    }
+
+    pub fn rotate(&mut self, rotation: Rotation) -> Result<(), EditError> {
+        let clipboard = self.clipboard.as_mut().ok_or(EditError::EmptyClipboard)?;
+        *clipboard = clipboard.rotated(rotation);
+        Ok(())
+    }
+
+    pub fn mirror(&mut self, axis: Axis) -> Result<(), EditError> {
+        let clipboard = self.clipboard.as_mut().ok_or(EditError::EmptyClipboard)?;
+        *clipboard = clipboard.mirrored(axis);
+        Ok(())
+    }
+
+    //returns false if there was nothing to undo
+    pub fn undo(&mut self, world: &mut World) -> bool {
+        match self.undo.pop() {
+            Some(edit) => {
+                world.set_blocks(edit.before.iter().copied());
+                self.redo.push(edit);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self, world: &mut World) -> bool {
+        match self.redo.pop() {
+            Some(edit) => {
+                world.set_blocks(edit.after.iter().copied());
+                self.undo.push(edit);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::generation::VoidGenerator;
+    use crate::world::position::ChunkPos;
+
+    fn world() -> World {
+        let mut world = World::new(1, Box::new(VoidGenerator));
+        world.generate_chunk(ChunkPos::new(0, 0));
+        world
+    }
+
+    fn select(editor: &mut Editor, a: BlockPos, b: BlockPos) {
+        editor.set_first_corner(a);
+        editor.set_second_corner(b);
+    }
+
+    //the blocks of the 2x2 square at y = 10 with its lowest corner at (x, z), row by row
+    fn square(world: &World, x: i32, z: i32) -> [Block; 4] {
+        [(0, 0), (1, 0), (0, 1), (1, 1)]
+            .map(|(dx, dz)| world.block(BlockPos::new(x + dx, 10, z + dz)))
+    }
+
+    //an L of stone, dirt and log in the 2x2 square at (0, 10, 0), copied to the clipboard
+    fn copied_l(world: &mut World, editor: &mut Editor) {
+        world.set_block(BlockPos::new(0, 10, 0), Block::Stone);
+        world.set_block(BlockPos::new(1, 10, 0), Block::Dirt);
+        world.set_block(BlockPos::new(0, 10, 1), Block::Log);
+        select(editor, BlockPos::new(0, 10, 0), BlockPos::new(1, 10, 1));
+        assert_eq!(editor.copy(world).unwrap(), 4);
+    }
+
+    #[test]
+    fn huge_selections_are_rejected() {
+        let mut world = world();
+        let mut editor = Editor::new();
+        select(
+            &mut editor,
+            BlockPos::new(0, 0, 0),
+            BlockPos::new(63, 49, 99),
+        );
+        assert!(matches!(
+            editor.fill(&mut world, Block::Stone),
+            Err(EditError::TooBig(320_000))
+        ));
+
+        let far = BlockPos::new(30_000_000, 30_000_000, 30_000_000);
+        select(&mut editor, BlockPos::new(-far.x, -far.y, -far.z), far);
+        assert_eq!(editor.selection().unwrap().volume(), None);
+        assert!(matches!(
+            editor.fill(&mut world, Block::Stone),
+            Err(EditError::Uncountable)
+        ));
+
+        let max = BlockPos::new(i32::MAX, i32::MAX, i32::MAX);
+        select(
+            &mut editor,
+            BlockPos::new(i32::MIN, i32::MIN, i32::MIN),
+            max,
+        );
+        assert!(matches!(
+            editor.fill(&mut world, Block::Stone),
+            Err(EditError::Uncountable)
+        ));
+    }
+
+    #[test]
+    fn replace_only_changes_blocks_of_the_same_kind() {
+        let mut world = world();
+        let mut editor = Editor::new();
+        world.set_block(BlockPos::new(0, 10, 0), Block::Water(2));
+        world.set_block(BlockPos::new(1, 10, 0), Block::Water(5));
+        world.set_block(BlockPos::new(0, 10, 1), Block::Dirt);
+
+        select(
+            &mut editor,
+            BlockPos::new(0, 10, 0),
+            BlockPos::new(1, 10, 1),
+        );
+        let changed = editor.replace(&mut world, Block::Water(0), Block::Glass);
+        assert_eq!(changed.unwrap(), 2);
+        assert_eq!(
+            square(&world, 0, 0),
+            [Block::Glass, Block::Glass, Block::Dirt, Block::Air]
+        );
+    }
+
+    #[test]
+    fn paste_places_the_clipboard_air_included() {
+        let mut world = world();
+        let mut editor = Editor::new();
+        assert!(matches!(
+            editor.paste(&mut world, BlockPos::new(0, 0, 0)),
+            Err(EditError::EmptyClipboard)
+        ));
+
+        copied_l(&mut world, &mut editor);
+        //the air in the clipboard replaces this too
+        world.set_block(BlockPos::new(6, 10, 6), Block::Obsidian);
+        assert_eq!(
+            editor.paste(&mut world, BlockPos::new(5, 10, 5)).unwrap(),
+            4
+        );
+        assert_eq!(
+            square(&world, 5, 5),
+            [Block::Stone, Block::Dirt, Block::Log, Block::Air]
+        );
+    }
+
+    #[test]
+    fn rotations_turn_the_clipboard_as_seen_from_above() {
+        let rotations = [
+            (
+                Rotation::Clockwise,
+                [Block::Log, Block::Stone, Block::Air, Block::Dirt],
+            ),
+            (
+                Rotation::Half,
+                [Block::Air, Block::Log, Block::Dirt, Block::Stone],
+            ),
+            (
+                Rotation::CounterClockwise,
+                [Block::Dirt, Block::Air, Block::Stone, Block::Log],
+            ),
+        ];
+        for (rotation, expected) in rotations {
+            let mut world = world();
+            let mut editor = Editor::new();
+            copied_l(&mut world, &mut editor);
+            editor.rotate(rotation).unwrap();
+            editor.paste(&mut world, BlockPos::new(4, 10, 4)).unwrap();
+            assert_eq!(square(&world, 4, 4), expected, "{:?}", rotation);
+        }
+
+        //quarter turns swap the width and the length
+        let mut world = world();
+        let mut editor = Editor::new();
+        select(&mut editor, BlockPos::new(0, 0, 0), BlockPos::new(2, 1, 0));
+        editor.copy(&world).unwrap();
+        editor.rotate(Rotation::Clockwise).unwrap();
+        assert_eq!(editor.clipboard().unwrap().size(), (1, 2, 3));
+        editor.rotate(Rotation::Half).unwrap();
+        assert_eq!(editor.clipboard().unwrap().size(), (1, 2, 3));
+        editor.rotate(Rotation::CounterClockwise).unwrap();
+        assert_eq!(editor.clipboard().unwrap().size(), (3, 2, 1));
+        assert!(editor.paste(&mut world, BlockPos::new(0, 0, 0)).is_ok());
+    }
+
+    #[test]
+    fn mirroring_flips_along_each_axis() {
+        let mirrors = [
+            (Axis::X, [Block::Dirt, Block::Stone, Block::Air, Block::Log]),
+            (Axis::Z, [Block::Log, Block::Air, Block::Stone, Block::Dirt]),
+        ];
+        for (axis, expected) in mirrors {
+            let mut world = world();
+            let mut editor = Editor::new();
+            copied_l(&mut world, &mut editor);
+            editor.mirror(axis).unwrap();
+            editor.paste(&mut world, BlockPos::new(4, 10, 4)).unwrap();
+            assert_eq!(square(&world, 4, 4), expected, "{:?}", axis);
+        }
+
+        let mut world = world();
+        let mut editor = Editor::new();
+        world.set_block(BlockPos::new(0, 10, 0), Block::Stone);
+        world.set_block(BlockPos::new(0, 11, 0), Block::Glass);
+        select(
+            &mut editor,
+            BlockPos::new(0, 10, 0),
+            BlockPos::new(0, 11, 0),
+        );
+        editor.copy(&world).unwrap();
+        editor.mirror(Axis::Y).unwrap();
+        editor.paste(&mut world, BlockPos::new(3, 20, 3)).unwrap();
+        assert_eq!(world.block(BlockPos::new(3, 20, 3)), Block::Glass);
+        assert_eq!(world.block(BlockPos::new(3, 21, 3)), Block::Stone);
+    }
+
+    #[test]
+    fn edits_are_undone_and_redone_in_order() {
+        let mut world = world();
+        let mut editor = Editor::new();
+        let pos = BlockPos::new(0, 10, 0);
+        select(&mut editor, pos, BlockPos::new(1, 10, 1));
+        for block in [Block::Stone, Block::Dirt, Block::Glass] {
+            editor.fill(&mut world, block).unwrap();
+        }
+        //nothing changes, so there's nothing to undo
+        assert_eq!(editor.fill(&mut world, Block::Glass).unwrap(), 0);
+
+        assert!(editor.undo(&mut world));
+        assert_eq!(world.block(pos), Block::Dirt);
+        assert!(editor.undo(&mut world));
+        assert_eq!(world.block(pos), Block::Stone);
+
+        assert!(editor.redo(&mut world));
+        assert_eq!(world.block(pos), Block::Dirt);
+        assert!(editor.undo(&mut world));
+        assert!(editor.undo(&mut world));
+        assert_eq!(square(&world, 0, 0), [Block::Air; 4]);
+        assert!(!editor.undo(&mut world));
+
+        //a new edit forgets what could be redone
+        editor.fill(&mut world, Block::Log).unwrap();
+        assert!(!editor.redo(&mut world));
+        assert!(editor.undo(&mut world));
+        assert_eq!(world.block(pos), Block::Air);
+        assert!(editor.redo(&mut world));
+        assert_eq!(world.block(pos), Block::Log);
+    }
+}
@@ -0,0 +1,98 @@
+use std::mem;
+
+use crate::utils::math;
+use crate::world::block::Block;
+use crate::world::position::BlockPos;
+use crate::world::World;
+
+//in blocks per tick squared
+const GRAVITY: f32 = 0.04;
+const DRAG: f32 = 0.98;
+
+//a block like sand which lost its support, it's taken out of the world while it falls and
+//placed back when it lands
+pub struct FallingBlock {
+    pub block: Block,
+    //lowest corner of the block
+    pub position: math::Vec3,
+    //upwards, in blocks per tick
+    velocity: f32,
+}
+
+pub fn start_falling(world: &mut World, pos: BlockPos) {
+    let block = world.block(pos);
+    world.set_block(pos, Block::Air);
+    world.falling_blocks_mut().push(FallingBlock {
+        block,
+        position: math::Vec3::new(pos.x as f32, pos.y as f32, pos.z as f32),
+        velocity: 0.0,
+    });
+}
+
+//moves every falling block by one tick, landing the ones which hit the ground
+pub fn update_falling_blocks(world: &mut World) {
+    //taken out of the world since landing blocks can make more blocks start falling
+    let mut falling = mem::take(world.falling_blocks_mut());
+
+    falling.retain_mut(|falling_block| {
+        falling_block.velocity = (falling_block.velocity - GRAVITY) * DRAG;
+        let y = falling_block.position.y + falling_block.velocity;
+        let (x, z) = (
+            falling_block.position.x.floor() as i32,
+            falling_block.position.z.floor() as i32,
+        );
+
+        //fast blocks move more than a block per tick, every block they pass through is checked so
+        //they can't skip through thin floors
+        let from = falling_block.position.y.floor() as i32;
+        let to = (y.floor() as i32).max(0);
+        for below_y in (to..=from).rev() {
+            let below = BlockPos::new(x, below_y, z);
+            if world.block(below).is_replaceable() {
+                continue;
+            }
+
+            //if the space it lands in got filled in the meantime the block is lost
+            let landing = below.offset(0, 1, 0);
+            if world.block(landing).is_replaceable() {
+                world.set_block(landing, falling_block.block);
+            }
+            return false;
+        }
+
+        //fell out of the bottom of the world
+        if y < 0.0 {
+            return false;
+        }
+        falling_block.position.y = y;
+        true
+    });
+
+    falling.append(world.falling_blocks_mut());
+    *world.falling_blocks_mut() = falling;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::generation::VoidGenerator;
+    use crate::world::position::ChunkPos;
+
+    #[test]
+    fn fast_blocks_land_on_thin_floors() {
+        //heights which a block dropped from the top of the world used to fall through
+        for floor in [3, 7, 11, 17, 26] {
+            let mut world = World::new(1, Box::new(VoidGenerator));
+            world.generate_chunk(ChunkPos::new(0, 0));
+            world.set_block(BlockPos::new(8, floor, 8), Block::Glass);
+            world.set_block(BlockPos::new(8, 49, 8), Block::Sand);
+
+            for _ in 0..200 {
+                world.tick();
+            }
+            assert!(world.falling_blocks().is_empty());
+            assert_eq!(world.block(BlockPos::new(8, floor + 1, 8)), Block::Sand);
+            assert_eq!(world.block(BlockPos::new(8, floor, 8)), Block::Glass);
+        }
+    }
+}
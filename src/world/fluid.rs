@@ -0,0 +1,301 @@
+use crate::world::block::Block;
+use crate::world::position::{BlockPos, Direction};
+use crate::world::World;
+
+//fluid blocks store how far away they are from the source they flow out of
+pub const SOURCE: u8 = 0;
+//fluid pouring down from the block above, spreads like a source once it lands
+pub const FALLING: u8 = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fluid {
+    Water,
+    Lava,
+}
+
+impl Fluid {
+    pub fn block(&self, level: u8) -> Block {
+        match self {
+            Fluid::Water => Block::Water(level),
+            Fluid::Lava => Block::Lava(level),
+        }
+    }
+
+    //ticks between two spreading steps
+    pub fn tick_rate(&self) -> u64 {
+        match self {
+            Fluid::Water => 5,
+            Fluid::Lava => 30,
+        }
+    }
+
+    //how many blocks the fluid can flow sideways from a source
+    fn max_distance(&self) -> u8 {
+        match self {
+            Fluid::Water => 7,
+            Fluid::Lava => 3,
+        }
+    }
+}
+
+//height of the fluid's surface inside of the block, from 0.0 to 1.0
+pub fn surface_height(level: u8) -> f32 {
+    if level == FALLING {
+        1.0
+    } else {
+        (8 - level) as f32 / 9.0
+    }
+}
+
+fn fluid_at(world: &World, pos: BlockPos) -> Option<(Fluid, u8)> {
+    world.block(pos).fluid()
+}
+
+//whether `fluid` is allowed to replace the block at `pos` with the given level
+fn can_flow_into(world: &World, pos: BlockPos, fluid: Fluid, level: u8) -> bool {
+    if !world.contains(pos) {
+        return false;
+    }
+
+    match world.block(pos) {
+        //torches get washed away
+        Block::Air | Block::Torch => true,
+        block => match block.fluid() {
+            Some((other, other_level)) if other == fluid => {
+                other_level != SOURCE && (level == FALLING || (other_level != FALLING && other_level > level))
+            }
+            //lava pouring into water is handled by `spread`
+            Some((Fluid::Water, _)) => fluid == Fluid::Lava && level == FALLING,
+            _ => false,
+        },
+    }
+}
+
+//level a flowing block should have based on its surroundings, None if it should dry up
+fn flowing_level(world: &World, pos: BlockPos, fluid: Fluid) -> Option<u8> {
+    if fluid_at(world, pos.neighbour(Direction::Up)).map(|(above, _)| above) == Some(fluid) {
+        return Some(FALLING);
+    }
+
+    let mut sources = 0;
+    let mut nearest = None;
+    for direction in Direction::HORIZONTAL {
+        if let Some((other, level)) = fluid_at(world, pos.neighbour(direction)) {
+            if other != fluid {
+                continue;
+            }
+            if level == SOURCE {
+                sources += 1;
+            }
+            let distance = if level == FALLING { 0 } else { level };
+            nearest = Some(nearest.map_or(distance, |nearest: u8| nearest.min(distance)));
+        }
+    }
+
+    //water between two sources becomes a source itself, as long as it doesn't just drain away
+    if fluid == Fluid::Water && sources >= 2 {
+        let below = world.block(pos.neighbour(Direction::Down));
+        if below.is_opaque() || below == Block::Water(SOURCE) {
+            return Some(SOURCE);
+        }
+    }
+
+    let level = nearest? + 1;
+    if level > fluid.max_distance() {
+        None
+    } else {
+        Some(level)
+    }
+}
+
+//lava touching water hardens, sources into obsidian and flowing lava into cobblestone
+fn harden_lava(world: &mut World, pos: BlockPos, level: u8) -> bool {
+    let touches_water = [Direction::Up, Direction::North, Direction::South, Direction::West, Direction::East]
+        .iter()
+        .any(|direction| {
+            matches!(fluid_at(world, pos.neighbour(*direction)), Some((Fluid::Water, _)))
+        });
+    if !touches_water {
+        return false;
+    }
+
+    let block = if level == SOURCE {
+        Block::Obsidian
+    } else {
+        Block::Cobblestone
+    };
+    world.set_block(pos, block);
+    true
+}
+
+fn spread(world: &mut World, pos: BlockPos, fluid: Fluid, level: u8) {
+    let below = pos.neighbour(Direction::Down);
+    if can_flow_into(world, below, fluid, FALLING) {
+        let block = match (fluid, fluid_at(world, below)) {
+            (Fluid::Lava, Some((Fluid::Water, _))) => Block::Stone,
+            _ => fluid.block(FALLING),
+        };
+        world.set_block(below, block);
+        return;
+    }
+
+    let spread_level = if level == FALLING { 1 } else { level + 1 };
+    if spread_level > fluid.max_distance() {
+        return;
+    }
+    for direction in Direction::HORIZONTAL {
+        let neighbour = pos.neighbour(direction);
+        if can_flow_into(world, neighbour, fluid, spread_level) {
+            world.set_block(neighbour, fluid.block(spread_level));
+        }
+    }
+}
+
+//scheduled update of a fluid block, first settles its own level and then flows into its neighbours
+pub fn update(world: &mut World, pos: BlockPos) {
+    let (fluid, level) = match fluid_at(world, pos) {
+        Some(fluid) => fluid,
+        None => return,
+    };
+
+    if fluid == Fluid::Lava && harden_lava(world, pos, level) {
+        return;
+    }
+
+    if level != SOURCE {
+        match flowing_level(world, pos, fluid) {
+            None => {
+                world.set_block(pos, Block::Air);
+                return;
+            }
+            //changing the block schedules another update, the spreading happens then
+            Some(new_level) if new_level != level => {
+                world.set_block(pos, fluid.block(new_level));
+                return;
+            }
+            Some(_) => (),
+        }
+    }
+
+    spread(world, pos, fluid, level);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::generation::FlatGenerator;
+    use crate::world::position::ChunkPos;
+
+    //a stone floor at y = 0 with nothing above it, 3x3 chunks around the origin
+    fn flat_world() -> World {
+        let mut world = World::new(1, Box::new(FlatGenerator::new("1*stone").unwrap()));
+        for x in -1..=1 {
+            for z in -1..=1 {
+                world.generate_chunk(ChunkPos::new(x, z));
+            }
+        }
+        world
+    }
+
+    fn run(world: &mut World, ticks: u64) {
+        for _ in 0..ticks {
+            world.tick();
+        }
+    }
+
+    #[test]
+    fn water_falls_one_block_per_step() {
+        let mut world = flat_world();
+        world.set_block(BlockPos::new(8, 4, 8), Block::Water(SOURCE));
+
+        run(&mut world, 5);
+        assert_eq!(world.block(BlockPos::new(8, 3, 8)), Block::Water(FALLING));
+        assert_eq!(world.block(BlockPos::new(8, 2, 8)), Block::Air);
+
+        run(&mut world, 10);
+        assert_eq!(world.block(BlockPos::new(8, 2, 8)), Block::Water(FALLING));
+        assert_eq!(world.block(BlockPos::new(8, 1, 8)), Block::Water(FALLING));
+        //falling water doesn't spread sideways while it's still in the air
+        assert_eq!(world.block(BlockPos::new(9, 3, 8)), Block::Air);
+    }
+
+    #[test]
+    fn water_spreads_outwards_after_landing() {
+        let mut world = flat_world();
+        world.set_block(BlockPos::new(8, 4, 8), Block::Water(SOURCE));
+
+        run(&mut world, 20);
+        for pos in [
+            BlockPos::new(7, 1, 8),
+            BlockPos::new(9, 1, 8),
+            BlockPos::new(8, 1, 7),
+            BlockPos::new(8, 1, 9),
+        ] {
+            assert_eq!(world.block(pos), Block::Water(1));
+        }
+        assert_eq!(world.block(BlockPos::new(6, 1, 8)), Block::Air);
+
+        run(&mut world, 40);
+        for distance in 1..=7 {
+            assert_eq!(
+                world.block(BlockPos::new(8 - distance, 1, 8)),
+                Block::Water(distance as u8)
+            );
+            assert_eq!(
+                world.block(BlockPos::new(8 + distance, 1, 8)),
+                Block::Water(distance as u8)
+            );
+        }
+        assert_eq!(world.block(BlockPos::new(0, 1, 8)), Block::Air);
+        //the column it fell down stays
+        assert_eq!(world.block(BlockPos::new(8, 4, 8)), Block::Water(SOURCE));
+        assert_eq!(world.block(BlockPos::new(8, 3, 8)), Block::Water(FALLING));
+        assert_eq!(world.block(BlockPos::new(8, 1, 8)), Block::Water(FALLING));
+    }
+
+    #[test]
+    fn water_between_two_sources_becomes_a_source() {
+        let mut world = flat_world();
+        //a trench three blocks long with a source at either end
+        for x in 4..=8 {
+            world.set_block(BlockPos::new(x, 1, 7), Block::Stone);
+            world.set_block(BlockPos::new(x, 1, 9), Block::Stone);
+        }
+        world.set_block(BlockPos::new(4, 1, 8), Block::Stone);
+        world.set_block(BlockPos::new(8, 1, 8), Block::Stone);
+        world.set_block(BlockPos::new(5, 1, 8), Block::Water(SOURCE));
+        world.set_block(BlockPos::new(7, 1, 8), Block::Water(SOURCE));
+
+        run(&mut world, 5);
+        assert_eq!(world.block(BlockPos::new(6, 1, 8)), Block::Water(1));
+        run(&mut world, 5);
+        assert_eq!(world.block(BlockPos::new(6, 1, 8)), Block::Water(SOURCE));
+    }
+
+    #[test]
+    fn lava_source_touching_water_turns_into_obsidian() {
+        let mut world = flat_world();
+        world.set_block(BlockPos::new(8, 1, 8), Block::Lava(SOURCE));
+        world.set_block(BlockPos::new(9, 1, 8), Block::Water(SOURCE));
+
+        run(&mut world, 29);
+        assert_eq!(world.block(BlockPos::new(8, 1, 8)), Block::Lava(SOURCE));
+        run(&mut world, 1);
+        assert_eq!(world.block(BlockPos::new(8, 1, 8)), Block::Obsidian);
+        assert_eq!(world.block(BlockPos::new(9, 1, 8)), Block::Water(SOURCE));
+    }
+
+    #[test]
+    fn flowing_lava_touching_water_turns_into_cobblestone() {
+        let mut world = flat_world();
+        world.set_block(BlockPos::new(2, 1, 8), Block::Lava(SOURCE));
+        run(&mut world, 30);
+        assert_eq!(world.block(BlockPos::new(3, 1, 8)), Block::Lava(1));
+
+        world.set_block(BlockPos::new(6, 1, 8), Block::Water(SOURCE));
+        run(&mut world, 30);
+        assert_eq!(world.block(BlockPos::new(3, 1, 8)), Block::Cobblestone);
+        assert_eq!(world.block(BlockPos::new(2, 1, 8)), Block::Lava(SOURCE));
+        assert_eq!(world.block(BlockPos::new(4, 1, 8)), Block::Water(2));
+    }
+}
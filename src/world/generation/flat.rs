@@ -0,0 +1,61 @@
+use crate::world::biome::Biome;
+use crate::world::block::Block;
+use crate::world::chunk::{ChunkData, CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH};
+use crate::world::generation::{GeneratorError, WorldGenerator};
+use crate::world::position::ChunkPos;
+
+//the same stack of layers everywhere
+pub struct FlatGenerator {
+    //one block for every height, starting at the bottom of the world
+    layers: Vec<Block>,
+}
+
+impl FlatGenerator {
+    //layers go from the bottom up, separated by commas, each being a block name with an optional
+    //count in front, like `1*stone,3*dirt,grass`
+    pub fn new(layers: &str) -> Result<Self, GeneratorError> {
+        let mut blocks = Vec::new();
+        for layer in layers
+            .split(',')
+            .map(str::trim)
+            .filter(|layer| !layer.is_empty())
+        {
+            let (count, name) = match layer.split_once('*') {
+                Some((count, name)) => {
+                    let count: usize = count
+                        .trim()
+                        .parse()
+                        .map_err(|_| GeneratorError::InvalidLayer(layer.to_string()))?;
+                    (count, name.trim())
+                }
+                None => (1, layer),
+            };
+            let block = Block::from_name(name)
+                .ok_or_else(|| GeneratorError::UnknownBlock(name.to_string()))?;
+            blocks.extend(std::iter::repeat_n(block, count));
+        }
+
+        if blocks.len() > CHUNK_HEIGHT {
+            return Err(GeneratorError::TooHigh(blocks.len()));
+        }
+        Ok(Self { layers: blocks })
+    }
+}
+
+impl WorldGenerator for FlatGenerator {
+    fn generate(&self, _pos: ChunkPos) -> ChunkData {
+        let mut chunk = ChunkData::new();
+        for (y, &block) in self.layers.iter().enumerate() {
+            for z in 0..CHUNK_DEPTH {
+                for x in 0..CHUNK_WIDTH {
+                    chunk.set_block(x, y, z, block);
+                }
+            }
+        }
+        chunk
+    }
+
+    fn biome_at(&self, _x: i32, _z: i32) -> Biome {
+        Biome::Plains
+    }
+}
@@ -0,0 +1,103 @@
+mod flat;
+mod noise;
+mod void;
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::world::biome::Biome;
+use crate::world::block::Block;
+use crate::world::chunk::ChunkData;
+use crate::world::ore;
+use crate::world::position::{BlockPos, ChunkPos};
+
+pub use flat::FlatGenerator;
+pub use noise::NoiseGenerator;
+pub use void::VoidGenerator;
+
+#[derive(Debug, Error)]
+pub enum GeneratorError {
+    #[error("Invalid superflat layer `{0}`, expected `count*block`")]
+    InvalidLayer(String),
+    #[error("Unknown block `{0}`")]
+    UnknownBlock(String),
+    #[error("Superflat layers are {0} blocks high, more than the height of the world")]
+    TooHigh(usize),
+    #[error(
+        "Invalid ore vein `{0}`, expected `ore:min_height-max_height:veins_per_chunk:vein_size`"
+    )]
+    InvalidOreVein(String),
+}
+
+//decides what new chunks look like
+pub trait WorldGenerator {
+    //the blocks of a chunk, without light
+    fn generate(&self, pos: ChunkPos) -> ChunkData;
+
+    //biomes only depend on the column, they can be asked for before the chunk is generated
+    fn biome_at(&self, x: i32, z: i32) -> Biome;
+
+    //blocks of the features rooted in the chunk, they may reach into the chunks around it
+    fn decorate(&self, _pos: ChunkPos, _chunk: &ChunkData) -> Vec<(BlockPos, Block)> {
+        Vec::new()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeneratorKind {
+    Noise,
+    Superflat,
+    Void,
+}
+
+impl GeneratorKind {
+    //the name used in config and level files
+    pub fn name(&self) -> &'static str {
+        match self {
+            GeneratorKind::Noise => "noise",
+            GeneratorKind::Superflat => "superflat",
+            GeneratorKind::Void => "void",
+        }
+    }
+}
+
+impl FromStr for GeneratorKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "noise" => Ok(GeneratorKind::Noise),
+            "superflat" => Ok(GeneratorKind::Superflat),
+            "void" => Ok(GeneratorKind::Void),
+            _ => Err(()),
+        }
+    }
+}
+
+//everything besides the seed that decides what a world generates, saved with the world so that
+//it keeps generating the same way once it's loaded again
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratorSettings {
+    pub kind: GeneratorKind,
+    //only used by the noise generator
+    pub caves: bool,
+    //only used by the superflat generator, see `FlatGenerator::new`
+    pub superflat_layers: String,
+    //only used by the noise generator, see `ore::parse_veins`
+    pub ores: String,
+}
+
+impl GeneratorSettings {
+    pub fn build(&self, seed: i64) -> Result<Box<dyn WorldGenerator>, GeneratorError> {
+        Ok(match self.kind {
+            GeneratorKind::Noise => Box::new(NoiseGenerator::new(
+                seed,
+                self.caves,
+                ore::parse_veins(&self.ores)?,
+            )),
+            GeneratorKind::Superflat => Box::new(FlatGenerator::new(&self.superflat_layers)?),
+            GeneratorKind::Void => Box::new(VoidGenerator),
+        })
+    }
+}
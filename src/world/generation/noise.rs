@@ -0,0 +1,187 @@
+use opensimplex_noise_rs::OpenSimplexNoise;
+
+use crate::utils::random::Random;
+use crate::world::biome::Biome;
+use crate::world::block::Block;
+use crate::world::chunk::{ChunkData, CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH};
+use crate::world::fluid::SOURCE;
+use crate::world::generation::WorldGenerator;
+use crate::world::ore::OreVein;
+use crate::world::position::{BlockPos, ChunkPos};
+
+const NOISE_SCALE: f64 = 0.044;
+//temperature and humidity change much slower than the terrain so that biomes are wide
+const CLIMATE_SCALE: f64 = 0.006;
+//the height settings of the biomes are averaged over the area around every column so that there
+//are no cliffs at the borders
+const BLEND_RADIUS: i32 = 8;
+const BLEND_STEP: usize = 4;
+const DIRT_DEPTH: usize = 3;
+//columns lower than this get filled with water
+const SEA_LEVEL: usize = 18;
+
+//caves are the tunnels where two 3d noise fields are both close to zero, the vertical scale is
+//higher so that they run mostly sideways
+const CAVE_SCALE: f64 = 0.06;
+const CAVE_VERTICAL_SCALE: f64 = 0.1;
+const CAVE_WIDTH: f64 = 0.09;
+//blocks of solid stone kept under the surface and above the bottom of the world
+const CAVE_ROOF: usize = 2;
+const CAVE_FLOOR: usize = 1;
+
+//hills, biomes, caves, ores and decorations
+pub struct NoiseGenerator {
+    seed: i64,
+    noise: OpenSimplexNoise,
+    temperature: OpenSimplexNoise,
+    humidity: OpenSimplexNoise,
+    //none when caves are disabled
+    cave_noise: Option<[OpenSimplexNoise; 2]>,
+    ores: Vec<OreVein>,
+}
+
+impl NoiseGenerator {
+    //the ores are placed in the order they're given, later ones can replace earlier ones
+    pub fn new(seed: i64, caves: bool, ores: Vec<OreVein>) -> Self {
+        let cave_noise = caves.then(|| {
+            [
+                OpenSimplexNoise::new(Some(seed.wrapping_add(1))),
+                OpenSimplexNoise::new(Some(seed.wrapping_add(2))),
+            ]
+        });
+        Self {
+            seed,
+            noise: OpenSimplexNoise::new(Some(seed)),
+            temperature: OpenSimplexNoise::new(Some(seed.wrapping_add(3))),
+            humidity: OpenSimplexNoise::new(Some(seed.wrapping_add(4))),
+            cave_noise,
+            ores,
+        }
+    }
+
+    //every chunk gets its own random numbers for each step of the generation, so that changing
+    //one step doesn't move everything placed by the others
+    fn chunk_random(&self, pos: ChunkPos, step: i64) -> Random {
+        Random::new(
+            self.seed
+                ^ (pos.x as i64).wrapping_mul(0x5DEE_CE66D)
+                ^ (pos.z as i64).wrapping_mul(0x2545_F491_4F6C_DD1D)
+                ^ step.wrapping_mul(0x1B87_3593),
+        )
+    }
+
+    fn height_at(&self, x: i32, z: i32) -> usize {
+        let mut base_height = 0.0;
+        let mut height_variation = 0.0;
+        let mut samples = 0.0;
+        for dz in (-BLEND_RADIUS..=BLEND_RADIUS).step_by(BLEND_STEP) {
+            for dx in (-BLEND_RADIUS..=BLEND_RADIUS).step_by(BLEND_STEP) {
+                let biome = self.biome_at(x + dx, z + dz);
+                base_height += biome.base_height();
+                height_variation += biome.height_variation();
+                samples += 1.0;
+            }
+        }
+
+        let value = self
+            .noise
+            .eval_2d(x as f64 * NOISE_SCALE, z as f64 * NOISE_SCALE);
+        let height = (base_height + value * height_variation) / samples;
+        (height as usize).clamp(1, CHUNK_HEIGHT - 1)
+    }
+
+    fn is_cave(&self, x: i32, y: usize, z: i32) -> bool {
+        let cave_noise = match &self.cave_noise {
+            Some(cave_noise) => cave_noise,
+            None => return false,
+        };
+        let (x, y, z) = (
+            x as f64 * CAVE_SCALE,
+            y as f64 * CAVE_VERTICAL_SCALE,
+            z as f64 * CAVE_SCALE,
+        );
+        cave_noise
+            .iter()
+            .all(|noise| noise.eval_3d(x, y, z).abs() < CAVE_WIDTH)
+    }
+}
+
+impl WorldGenerator for NoiseGenerator {
+    fn biome_at(&self, x: i32, z: i32) -> Biome {
+        let (x, z) = (x as f64 * CLIMATE_SCALE, z as f64 * CLIMATE_SCALE);
+        Biome::from_climate(self.temperature.eval_2d(x, z), self.humidity.eval_2d(x, z))
+    }
+
+    fn generate(&self, pos: ChunkPos) -> ChunkData {
+        let mut chunk = ChunkData::new();
+        let origin = pos.origin();
+
+        for z in 0..CHUNK_DEPTH {
+            for x in 0..CHUNK_WIDTH {
+                let (world_x, world_z) = (origin.x + x as i32, origin.z + z as i32);
+                let height = self.height_at(world_x, world_z);
+                //columns near or under the water get sand whatever the biome
+                let (surface, filler) = if height <= SEA_LEVEL + 1 {
+                    (Block::Sand, Block::Sand)
+                } else {
+                    let biome = self.biome_at(world_x, world_z);
+                    (biome.surface_block(), biome.filler_block())
+                };
+                //the roof keeps caves from opening into the sea floor and letting the water in
+                let cave_top = height.saturating_sub(DIRT_DEPTH + 1 + CAVE_ROOF);
+                for y in 0..height {
+                    if y >= CAVE_FLOOR && y < cave_top && self.is_cave(world_x, y, world_z) {
+                        continue;
+                    }
+                    let block = if y == height - 1 {
+                        surface
+                    } else if y + DIRT_DEPTH >= height - 1 {
+                        filler
+                    } else {
+                        Block::Stone
+                    };
+                    chunk.set_block(x, y, z, block);
+                }
+                for y in height..SEA_LEVEL {
+                    chunk.set_block(x, y, z, Block::Water(SOURCE));
+                }
+            }
+        }
+
+        //ores only replace stone so they can show up in the walls of caves
+        let mut random = self.chunk_random(pos, 0);
+        for ore in &self.ores {
+            ore.place(&mut chunk, &mut random);
+        }
+
+        chunk
+    }
+
+    //the features rooted in the chunk, which may reach into the chunks around it, placed in the
+    //same way for a seed whatever order the chunks are generated in
+    fn decorate(&self, pos: ChunkPos, chunk: &ChunkData) -> Vec<(BlockPos, Block)> {
+        let mut random = self.chunk_random(pos, 1);
+        let centre = pos.block_pos(CHUNK_WIDTH / 2, 0, CHUNK_DEPTH / 2);
+        let biome = self.biome_at(centre.x, centre.z);
+
+        let mut blocks = Vec::new();
+        for &(feature, tries) in biome.decorations() {
+            for _ in 0..tries {
+                let x = random.next_below(CHUNK_WIDTH as u32) as usize;
+                let z = random.next_below(CHUNK_DEPTH as u32) as usize;
+                let ground = match (0..CHUNK_HEIGHT)
+                    .rev()
+                    .find(|&y| chunk.block(x, y, z) != Block::Air)
+                {
+                    Some(ground) => ground,
+                    None => continue,
+                };
+                if feature.can_stand_on(chunk.block(x, ground, z)) {
+                    feature.place(&mut random, pos.block_pos(x, ground + 1, z), &mut blocks);
+                }
+            }
+        }
+
+        blocks
+    }
+}
@@ -0,0 +1,17 @@
+use crate::world::biome::Biome;
+use crate::world::chunk::ChunkData;
+use crate::world::generation::WorldGenerator;
+use crate::world::position::ChunkPos;
+
+//nothing but air, for building and testing from scratch
+pub struct VoidGenerator;
+
+impl WorldGenerator for VoidGenerator {
+    fn generate(&self, _pos: ChunkPos) -> ChunkData {
+        ChunkData::new()
+    }
+
+    fn biome_at(&self, _x: i32, _z: i32) -> Biome {
+        Biome::Plains
+    }
+}
@@ -0,0 +1,228 @@
+use crate::world::block::Block;
+
+pub const INVENTORY_SIZE: usize = 36;
+//the first slots of the inventory, one of them is held
+pub const HOTBAR_SIZE: usize = 9;
+pub const MAX_STACK_SIZE: u32 = 64;
+
+//some of one kind of item, never empty
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemStack {
+    pub item: Block,
+    pub count: u32,
+}
+
+impl ItemStack {
+    pub fn new(item: Block, count: u32) -> Self {
+        Self { item, count }
+    }
+}
+
+pub struct Inventory {
+    slots: [Option<ItemStack>; INVENTORY_SIZE],
+    //index of the held hotbar slot
+    selected: usize,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self {
+            slots: [None; INVENTORY_SIZE],
+            selected: 0,
+        }
+    }
+
+    pub fn slots(&self) -> &[Option<ItemStack>] {
+        &self.slots
+    }
+
+    pub fn hotbar(&self) -> &[Option<ItemStack>] {
+        &self.slots[..HOTBAR_SIZE]
+    }
+
+    pub fn slot(&self, index: usize) -> Option<ItemStack> {
+        self.slots[index]
+    }
+
+    //stacks without any items empty the slot
+    pub fn set_slot(&mut self, index: usize, stack: Option<ItemStack>) {
+        self.slots[index] = stack.filter(|stack| stack.count > 0);
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    //slots past the hotbar are ignored
+    pub fn select(&mut self, index: usize) {
+        if index < HOTBAR_SIZE {
+            self.selected = index;
+        }
+    }
+
+    //moves the selection by `steps` slots, wrapping around at either end of the hotbar
+    pub fn scroll(&mut self, steps: i32) {
+        self.selected = (self.selected as i32 + steps).rem_euclid(HOTBAR_SIZE as i32) as usize;
+    }
+
+    pub fn selected_stack(&self) -> Option<ItemStack> {
+        self.slots[self.selected]
+    }
+
+    //tops up the stacks of the item there already are before starting new ones, going through
+    //the hotbar first. returns how many items didn't fit
+    pub fn add(&mut self, item: Block, count: u32) -> u32 {
+        let mut left = count;
+        for slot in self.slots.iter_mut().flatten() {
+            if left == 0 {
+                break;
+            }
+            if slot.item == item {
+                let added = left.min(MAX_STACK_SIZE.saturating_sub(slot.count));
+                slot.count += added;
+                left -= added;
+            }
+        }
+        for slot in self.slots.iter_mut() {
+            if left == 0 {
+                break;
+            }
+            if slot.is_none() {
+                let added = left.min(MAX_STACK_SIZE);
+                *slot = Some(ItemStack::new(item, added));
+                left -= added;
+            }
+        }
+        left
+    }
+
+    //takes one item out of the held stack
+    pub fn take_selected(&mut self) -> Option<Block> {
+        let stack = self.slots[self.selected].as_mut()?;
+        let item = stack.item;
+        stack.count -= 1;
+        if stack.count == 0 {
+            self.slots[self.selected] = None;
+        }
+        Some(item)
+    }
+
+    pub fn count(&self, item: Block) -> u32 {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(|stack| stack.item == item)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    //takes the items from the last stacks first, only if there are enough of them
+    pub fn remove(&mut self, item: Block, count: u32) -> bool {
+        if self.count(item) < count {
+            return false;
+        }
+        let mut left = count;
+        for slot in self.slots.iter_mut().rev() {
+            if left == 0 {
+                break;
+            }
+            if let Some(stack) = slot {
+                if stack.item == item {
+                    let removed = left.min(stack.count);
+                    stack.count -= removed;
+                    left -= removed;
+                    if stack.count == 0 {
+                        *slot = None;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.slots = [None; INVENTORY_SIZE];
+    }
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_tops_up_stacks_before_using_empty_slots() {
+        let mut inventory = Inventory::new();
+        inventory.set_slot(0, Some(ItemStack::new(Block::Dirt, 10)));
+        inventory.set_slot(3, Some(ItemStack::new(Block::Stone, 60)));
+
+        assert_eq!(inventory.add(Block::Stone, 10), 0);
+        assert_eq!(inventory.slot(3), Some(ItemStack::new(Block::Stone, 64)));
+        //the first empty slot
+        assert_eq!(inventory.slot(1), Some(ItemStack::new(Block::Stone, 6)));
+        assert_eq!(inventory.slot(2), None);
+    }
+
+    #[test]
+    fn add_returns_what_didnt_fit() {
+        let mut inventory = Inventory::new();
+        let room = INVENTORY_SIZE as u32 * MAX_STACK_SIZE;
+        assert_eq!(inventory.add(Block::Sand, room + 5), 5);
+        assert_eq!(inventory.count(Block::Sand), room);
+        assert_eq!(inventory.add(Block::Gravel, 1), 1);
+    }
+
+    #[test]
+    fn taking_the_last_item_empties_the_slot() {
+        let mut inventory = Inventory::new();
+        inventory.add(Block::Torch, 2);
+        assert_eq!(inventory.take_selected(), Some(Block::Torch));
+        assert_eq!(
+            inventory.selected_stack(),
+            Some(ItemStack::new(Block::Torch, 1))
+        );
+        assert_eq!(inventory.take_selected(), Some(Block::Torch));
+        assert_eq!(inventory.selected_stack(), None);
+        assert_eq!(inventory.take_selected(), None);
+    }
+
+    #[test]
+    fn remove_takes_all_or_nothing_from_the_last_stacks_first() {
+        let mut inventory = Inventory::new();
+        inventory.set_slot(0, Some(ItemStack::new(Block::Log, 5)));
+        inventory.set_slot(20, Some(ItemStack::new(Block::Log, 3)));
+
+        assert!(!inventory.remove(Block::Log, 9));
+        assert_eq!(inventory.count(Block::Log), 8);
+
+        assert!(inventory.remove(Block::Log, 4));
+        assert_eq!(inventory.slot(20), None);
+        assert_eq!(inventory.slot(0), Some(ItemStack::new(Block::Log, 4)));
+    }
+
+    #[test]
+    fn scrolling_wraps_around_the_hotbar() {
+        let mut inventory = Inventory::new();
+        inventory.scroll(-1);
+        assert_eq!(inventory.selected(), HOTBAR_SIZE - 1);
+        inventory.scroll(2);
+        assert_eq!(inventory.selected(), 1);
+        inventory.scroll(-(HOTBAR_SIZE as i32) * 3);
+        assert_eq!(inventory.selected(), 1);
+    }
+
+    #[test]
+    fn only_hotbar_slots_can_be_selected() {
+        let mut inventory = Inventory::new();
+        inventory.select(4);
+        assert_eq!(inventory.selected(), 4);
+        inventory.select(HOTBAR_SIZE);
+        inventory.select(INVENTORY_SIZE - 1);
+        assert_eq!(inventory.selected(), 4);
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+
+use crate::world::block::Block;
+use crate::world::chunk::{CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH, MAX_LIGHT};
+use crate::world::position::{BlockPos, ChunkPos, Direction};
+use crate::world::World;
+
+//sky light comes from the top of the world, block light from emissive blocks (torches, lava...)
+//both spread the same way, losing one level per block, except for full sky light which
+//travels straight down without getting any dimmer
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LightKind {
+    Sky,
+    Block,
+}
+
+const LIGHT_KINDS: [LightKind; 2] = [LightKind::Sky, LightKind::Block];
+
+fn light(world: &World, kind: LightKind, pos: BlockPos) -> u8 {
+    match kind {
+        LightKind::Sky => world.sky_light(pos),
+        LightKind::Block => world.block_light(pos),
+    }
+}
+
+fn set_light(world: &mut World, kind: LightKind, pos: BlockPos, level: u8) {
+    match kind {
+        LightKind::Sky => world.set_sky_light(pos, level),
+        LightKind::Block => world.set_block_light(pos, level),
+    }
+}
+
+//light a block has on its own, regardless of its neighbours
+fn source_level(kind: LightKind, pos: BlockPos, block: Block) -> u8 {
+    match kind {
+        LightKind::Sky if pos.y == CHUNK_HEIGHT as i32 - 1 && !block.is_opaque() => MAX_LIGHT,
+        LightKind::Sky => 0,
+        LightKind::Block => block.light_emission(),
+    }
+}
+
+fn propagated_level(kind: LightKind, level: u8, direction: Direction) -> u8 {
+    if kind == LightKind::Sky && direction == Direction::Down && level == MAX_LIGHT {
+        MAX_LIGHT
+    } else {
+        level.saturating_sub(1)
+    }
+}
+
+//breadth first flood fill from every position in the queue
+fn propagate(world: &mut World, kind: LightKind, queue: &mut VecDeque<BlockPos>) {
+    while let Some(pos) = queue.pop_front() {
+        let level = light(world, kind, pos);
+        for direction in Direction::ALL {
+            let neighbour = pos.neighbour(direction);
+            if !world.contains(neighbour) || world.block(neighbour).is_opaque() {
+                continue;
+            }
+
+            let new_level = propagated_level(kind, level, direction);
+            if light(world, kind, neighbour) < new_level {
+                set_light(world, kind, neighbour, new_level);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+}
+
+//darkens everything that was lit by the removed light, neighbours that are brighter than what
+//was removed have another light source so they end up in `queue` to fill the hole back in
+fn unpropagate(
+    world: &mut World,
+    kind: LightKind,
+    removal: &mut VecDeque<(BlockPos, u8)>,
+    queue: &mut VecDeque<BlockPos>,
+) {
+    while let Some((pos, level)) = removal.pop_front() {
+        for direction in Direction::ALL {
+            let neighbour = pos.neighbour(direction);
+            if !world.contains(neighbour) {
+                continue;
+            }
+
+            let neighbour_level = light(world, kind, neighbour);
+            if neighbour_level == 0 {
+                continue;
+            }
+
+            let lit_by_pos = neighbour_level < level
+                || (kind == LightKind::Sky && direction == Direction::Down && level == MAX_LIGHT);
+            if lit_by_pos {
+                let source = source_level(kind, neighbour, world.block(neighbour));
+                set_light(world, kind, neighbour, source);
+                removal.push_back((neighbour, neighbour_level));
+                if source > 0 {
+                    queue.push_back(neighbour);
+                }
+            } else {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+}
+
+//incrementally fixes up the light around a block which was just replaced with `block`
+pub fn block_changed(world: &mut World, pos: BlockPos, block: Block) {
+    for kind in LIGHT_KINDS {
+        let mut queue = VecDeque::new();
+        let mut removal = VecDeque::new();
+
+        let current = light(world, kind, pos);
+        let source = source_level(kind, pos, block);
+        set_light(world, kind, pos, source);
+        if current > 0 {
+            removal.push_back((pos, current));
+        }
+        unpropagate(world, kind, &mut removal, &mut queue);
+
+        if source > 0 {
+            queue.push_back(pos);
+        }
+        //the block no longer stops light so the neighbours can shine into it
+        if !block.is_opaque() {
+            for direction in Direction::ALL {
+                let neighbour = pos.neighbour(direction);
+                if world.contains(neighbour) && light(world, kind, neighbour) > 0 {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        propagate(world, kind, &mut queue);
+    }
+}
+
+//computes the light of a freshly loaded chunk, pulling in light from already loaded neighbours
+//and pushing its own light into them
+pub fn light_chunk(world: &mut World, chunk_pos: ChunkPos) {
+    let mut sky_queue = VecDeque::new();
+    let mut block_queue = VecDeque::new();
+
+    for z in 0..CHUNK_DEPTH {
+        for x in 0..CHUNK_WIDTH {
+            let mut sky_visible = true;
+            for y in (0..CHUNK_HEIGHT).rev() {
+                let pos = chunk_pos.block_pos(x, y, z);
+                let block = world.block(pos);
+                if block.is_opaque() {
+                    sky_visible = false;
+                }
+                if sky_visible {
+                    world.set_sky_light(pos, MAX_LIGHT);
+                    sky_queue.push_back(pos);
+                }
+
+                let emission = block.light_emission();
+                if emission > 0 {
+                    world.set_block_light(pos, emission);
+                    block_queue.push_back(pos);
+                }
+            }
+        }
+    }
+
+    let origin = chunk_pos.origin();
+    for y in 0..CHUNK_HEIGHT as i32 {
+        for i in 0..CHUNK_WIDTH.max(CHUNK_DEPTH) as i32 {
+            let border = [
+                origin.offset(-1, y, i),
+                origin.offset(CHUNK_WIDTH as i32, y, i),
+                origin.offset(i, y, -1),
+                origin.offset(i, y, CHUNK_DEPTH as i32),
+            ];
+            for pos in border {
+                if !world.contains(pos) || pos.chunk_pos() == chunk_pos {
+                    continue;
+                }
+                if world.sky_light(pos) > 0 {
+                    sky_queue.push_back(pos);
+                }
+                if world.block_light(pos) > 0 {
+                    block_queue.push_back(pos);
+                }
+            }
+        }
+    }
+
+    propagate(world, LightKind::Sky, &mut sky_queue);
+    propagate(world, LightKind::Block, &mut block_queue);
+}
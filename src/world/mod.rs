@@ -0,0 +1,28 @@
+mod behaviour;
+pub mod biome;
+pub mod block;
+pub mod chunk;
+pub mod crafting;
+mod decoration;
+pub mod edit;
+pub mod entity;
+pub mod fluid;
+pub mod generation;
+pub mod inventory;
+mod light;
+pub mod ore;
+pub mod player;
+pub mod position;
+mod raycast;
+pub mod save;
+pub mod schematic;
+mod tick;
+pub mod time;
+//`world::world` repeats its parent's name, which is harmless since `World` is re-exported below
+//and the inner module never shows up outside
+#[allow(clippy::module_inception)]
+mod world;
+
+pub use block::Block;
+pub use position::{BlockPos, ChunkPos, Direction};
+pub use world::World;
@@ -0,0 +1,220 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::utils::random::Random;
+use crate::world::block::Block;
+use crate::world::chunk::{ChunkData, CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH};
+use crate::world::generation::GeneratorError;
+use crate::world::position::Direction;
+
+//how one kind of ore is spread through the stone
+#[derive(Clone, Copy, Debug)]
+pub struct OreVein {
+    pub ore: Block,
+    //lowest and highest block a vein can start at, both included
+    pub min_height: usize,
+    pub max_height: usize,
+    pub veins_per_chunk: u32,
+    //blocks the vein tries to place, fewer end up in the world if it runs into anything but stone
+    pub vein_size: u32,
+}
+
+//the ores of the `ores` config key when it isn't set
+pub const ORES: [OreVein; 4] = [
+    OreVein {
+        ore: Block::CoalOre,
+        min_height: 5,
+        max_height: 45,
+        veins_per_chunk: 10,
+        vein_size: 12,
+    },
+    OreVein {
+        ore: Block::IronOre,
+        min_height: 3,
+        max_height: 32,
+        veins_per_chunk: 6,
+        vein_size: 8,
+    },
+    OreVein {
+        ore: Block::GoldOre,
+        min_height: 2,
+        max_height: 18,
+        veins_per_chunk: 2,
+        vein_size: 6,
+    },
+    OreVein {
+        ore: Block::DiamondOre,
+        min_height: 1,
+        max_height: 10,
+        veins_per_chunk: 1,
+        vein_size: 4,
+    },
+];
+
+//a list of veins separated by commas, like `coal_ore:5-45:10:12,iron_ore:3-32:6:8`
+pub fn parse_veins(veins: &str) -> Result<Vec<OreVein>, GeneratorError> {
+    veins
+        .split(',')
+        .map(str::trim)
+        .filter(|vein| !vein.is_empty())
+        .map(|vein| {
+            vein.parse()
+                .map_err(|_| GeneratorError::InvalidOreVein(vein.to_string()))
+        })
+        .collect()
+}
+
+//the other way around from `parse_veins`
+pub fn format_veins(veins: &[OreVein]) -> String {
+    let veins: Vec<String> = veins.iter().map(OreVein::to_string).collect();
+    veins.join(",")
+}
+
+//`ore:min_height-max_height:veins_per_chunk:vein_size`
+impl FromStr for OreVein {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [ore, heights, veins_per_chunk, vein_size] = s.split(':').collect::<Vec<_>>()[..]
+        else {
+            return Err(());
+        };
+        let (min_height, max_height) = heights.split_once('-').ok_or(())?;
+        let vein = OreVein {
+            ore: Block::from_name(ore.trim())
+                .filter(|ore| *ore != Block::Air)
+                .ok_or(())?,
+            min_height: min_height.trim().parse().map_err(|_| ())?,
+            max_height: max_height.trim().parse().map_err(|_| ())?,
+            veins_per_chunk: veins_per_chunk.trim().parse().map_err(|_| ())?,
+            vein_size: vein_size.trim().parse().map_err(|_| ())?,
+        };
+        if vein.min_height > vein.max_height || vein.min_height >= CHUNK_HEIGHT {
+            return Err(());
+        }
+        Ok(vein)
+    }
+}
+
+impl fmt::Display for OreVein {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}-{}:{}:{}",
+            self.ore.name(),
+            self.min_height,
+            self.max_height,
+            self.veins_per_chunk,
+            self.vein_size
+        )
+    }
+}
+
+impl OreVein {
+    //veins wander randomly from where they start but never leave the chunk or their height range,
+    //so every chunk's ores only depend on its own seed
+    pub fn place(&self, chunk: &mut ChunkData, random: &mut Random) {
+        let max_height = self.max_height.min(CHUNK_HEIGHT - 1);
+        for _ in 0..self.veins_per_chunk {
+            let mut x = random.next_below(CHUNK_WIDTH as u32) as i32;
+            let mut y = random.range(self.min_height as i32, max_height as i32);
+            let mut z = random.next_below(CHUNK_DEPTH as u32) as i32;
+
+            for _ in 0..self.vein_size {
+                let (lx, ly, lz) = (x as usize, y as usize, z as usize);
+                if chunk.block(lx, ly, lz) == Block::Stone {
+                    chunk.set_block(lx, ly, lz, self.ore);
+                }
+
+                let (dx, dy, dz) = Direction::ALL[random.next_below(6) as usize].offset();
+                x = (x + dx).clamp(0, CHUNK_WIDTH as i32 - 1);
+                y = (y + dy).clamp(self.min_height as i32, max_height as i32);
+                z = (z + dz).clamp(0, CHUNK_DEPTH as i32 - 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stone_chunk() -> ChunkData {
+        let mut chunk = ChunkData::new();
+        for y in 0..CHUNK_HEIGHT {
+            for z in 0..CHUNK_DEPTH {
+                for x in 0..CHUNK_WIDTH {
+                    chunk.set_block(x, y, z, Block::Stone);
+                }
+            }
+        }
+        chunk
+    }
+
+    //how many of every ore there are, in the order of the veins
+    fn count_ores(veins: &[OreVein], seed: i64) -> Vec<u32> {
+        let mut chunk = stone_chunk();
+        let mut random = Random::new(seed);
+        for vein in veins {
+            vein.place(&mut chunk, &mut random);
+        }
+
+        let mut counts = vec![0; veins.len()];
+        for y in 0..CHUNK_HEIGHT {
+            for z in 0..CHUNK_DEPTH {
+                for x in 0..CHUNK_WIDTH {
+                    let block = chunk.block(x, y, z);
+                    if let Some(index) = veins.iter().position(|vein| vein.ore == block) {
+                        assert!(y >= veins[index].min_height && y <= veins[index].max_height);
+                        counts[index] += 1;
+                    }
+                }
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn counts_only_depend_on_the_seed() {
+        let counts = count_ores(&ORES, 42);
+        assert_eq!(counts, count_ores(&ORES, 42));
+        assert_ne!(counts, count_ores(&ORES, 43));
+        for (count, vein) in counts.iter().zip(ORES) {
+            assert!(*count > 0);
+            assert!(*count <= vein.veins_per_chunk * vein.vein_size);
+        }
+    }
+
+    #[test]
+    fn configured_veins_are_used() {
+        let veins = parse_veins("gold_ore:20-20:3:5, obsidian:1-2:0:10").unwrap();
+        assert_eq!(veins.len(), 2);
+        let counts = count_ores(&veins, 7);
+        assert!(counts[0] > 0 && counts[0] <= 15);
+        assert_eq!(counts[1], 0);
+    }
+
+    #[test]
+    fn veins_are_parsed_and_formatted() {
+        let formatted = format_veins(&ORES);
+        assert_eq!(
+            formatted,
+            "coal_ore:5-45:10:12,iron_ore:3-32:6:8,gold_ore:2-18:2:6,diamond_ore:1-10:1:4"
+        );
+        let parsed = parse_veins(&formatted).unwrap();
+        assert_eq!(format_veins(&parsed), formatted);
+
+        for invalid in [
+            "coal_ore",
+            "coal_ore:5:10:12",
+            "air:5-45:10:12",
+            "unknown:5-45:10:12",
+            "coal_ore:45-5:10:12",
+            "coal_ore:5-45:ten:12",
+            "coal_ore:5-45:10:12:1",
+        ] {
+            assert!(parse_veins(invalid).is_err(), "{}", invalid);
+        }
+        assert!(parse_veins("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use std::str::FromStr;
+
+use crate::utils::math;
+use crate::world::block::Block;
+use crate::world::inventory::{Inventory, MAX_STACK_SIZE};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameMode {
+    //blocks can be placed without running out and broken ones aren't picked up
+    Creative,
+    //placing blocks uses up the held ones and breaking blocks collects them
+    Survival,
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Creative => "creative",
+            GameMode::Survival => "survival",
+        }
+    }
+}
+
+impl FromStr for GameMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "creative" | "c" | "1" => Ok(GameMode::Creative),
+            "survival" | "s" | "0" => Ok(GameMode::Survival),
+            _ => Err(()),
+        }
+    }
+}
+
+//what the game knows about the player besides the camera
+pub struct Player {
+    //where the eyes are
+    pub position: math::Vec3,
+    pub game_mode: GameMode,
+    pub inventory: Inventory,
+}
+
+impl Player {
+    //starts out holding torches
+    pub fn new(position: math::Vec3) -> Self {
+        let mut inventory = Inventory::new();
+        inventory.add(Block::Torch, MAX_STACK_SIZE);
+        Self {
+            position,
+            game_mode: GameMode::Creative,
+            inventory,
+        }
+    }
+
+    //the block placed with right click, if any
+    pub fn held_block(&self) -> Option<Block> {
+        self.inventory.selected_stack().map(|stack| stack.item)
+    }
+
+    //takes the held block out of the inventory unless it's creative, where it never runs out
+    pub fn use_held_block(&mut self) -> Option<Block> {
+        match self.game_mode {
+            GameMode::Creative => self.held_block(),
+            GameMode::Survival => self.inventory.take_selected(),
+        }
+    }
+
+    //picks up what the block drops in survival. returns whether it fit in the inventory
+    pub fn collect(&mut self, broken: Block) -> bool {
+        match (self.game_mode, broken.drop()) {
+            (GameMode::Survival, Some(item)) => self.inventory.add(item, 1) == 0,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::inventory::{ItemStack, INVENTORY_SIZE};
+
+    fn player(game_mode: GameMode) -> Player {
+        let mut player = Player::new(math::Vec3::new(0.0, 0.0, 0.0));
+        player.game_mode = game_mode;
+        player.inventory.clear();
+        player.inventory.add(Block::Stone, 1);
+        player
+    }
+
+    #[test]
+    fn creative_never_runs_out_or_picks_up() {
+        let mut player = player(GameMode::Creative);
+        assert_eq!(player.use_held_block(), Some(Block::Stone));
+        assert_eq!(player.use_held_block(), Some(Block::Stone));
+        assert!(player.collect(Block::Dirt));
+        assert_eq!(player.inventory.count(Block::Dirt), 0);
+    }
+
+    #[test]
+    fn survival_uses_up_and_collects_blocks() {
+        let mut player = player(GameMode::Survival);
+        assert_eq!(player.use_held_block(), Some(Block::Stone));
+        assert_eq!(player.use_held_block(), None);
+        assert_eq!(player.held_block(), None);
+
+        assert!(player.collect(Block::Stone));
+        assert_eq!(player.inventory.count(Block::Cobblestone), 1);
+        //nothing to pick up
+        assert!(player.collect(Block::Glass));
+        assert_eq!(player.inventory.slot(1), None);
+
+        for slot in 0..INVENTORY_SIZE {
+            player
+                .inventory
+                .set_slot(slot, Some(ItemStack::new(Block::Sand, MAX_STACK_SIZE)));
+        }
+        assert!(!player.collect(Block::Dirt));
+    }
+}
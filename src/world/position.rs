@@ -0,0 +1,105 @@
+use crate::world::chunk::{CHUNK_DEPTH, CHUNK_WIDTH};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 6] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+
+    pub const HORIZONTAL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+
+    pub fn offset(&self) -> (i32, i32, i32) {
+        match self {
+            Direction::Up => (0, 1, 0),
+            Direction::Down => (0, -1, 0),
+            Direction::North => (0, 0, -1),
+            Direction::South => (0, 0, 1),
+            Direction::West => (-1, 0, 0),
+            Direction::East => (1, 0, 0),
+        }
+    }
+}
+
+//position of a single block in world space
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlockPos {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl BlockPos {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn offset(&self, dx: i32, dy: i32, dz: i32) -> Self {
+        Self::new(self.x + dx, self.y + dy, self.z + dz)
+    }
+
+    pub fn neighbour(&self, direction: Direction) -> Self {
+        let (dx, dy, dz) = direction.offset();
+        self.offset(dx, dy, dz)
+    }
+
+    pub fn chunk_pos(&self) -> ChunkPos {
+        ChunkPos::new(
+            self.x.div_euclid(CHUNK_WIDTH as i32),
+            self.z.div_euclid(CHUNK_DEPTH as i32),
+        )
+    }
+
+    //coordinates inside of the chunk the block belongs to
+    pub fn local(&self) -> (usize, usize, usize) {
+        (
+            self.x.rem_euclid(CHUNK_WIDTH as i32) as usize,
+            self.y as usize,
+            self.z.rem_euclid(CHUNK_DEPTH as i32) as usize,
+        )
+    }
+}
+
+//chunks are full height columns so they are only indexed on the x and z axis
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChunkPos {
+    pub x: i32,
+    pub z: i32,
+}
+
+impl ChunkPos {
+    pub fn new(x: i32, z: i32) -> Self {
+        Self { x, z }
+    }
+
+    //world position of the chunk's (0, 0, 0) block
+    pub fn origin(&self) -> BlockPos {
+        BlockPos::new(
+            self.x * CHUNK_WIDTH as i32,
+            0,
+            self.z * CHUNK_DEPTH as i32,
+        )
+    }
+
+    pub fn block_pos(&self, x: usize, y: usize, z: usize) -> BlockPos {
+        self.origin().offset(x as i32, y as i32, z as i32)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::utils::math;
+use crate::world::position::{BlockPos, Direction};
+use crate::world::World;
+
+pub struct RaycastHit {
+    pub pos: BlockPos,
+    //face of the block the ray entered through, None if the ray started inside of the block
+    pub face: Option<Direction>,
+}
+
+impl World {
+    //walks the grid voxel by voxel along the ray (Amanatides & Woo), `direction` has to be normalized
+    pub fn raycast(
+        &self,
+        origin: &math::Vec3,
+        direction: &math::Vec3,
+        max_distance: f32,
+    ) -> Option<RaycastHit> {
+        let mut pos = BlockPos::new(
+            origin.x.floor() as i32,
+            origin.y.floor() as i32,
+            origin.z.floor() as i32,
+        );
+        let mut face = None;
+
+        let axis = |origin: f32, direction: f32| -> (i32, f32, f32) {
+            if direction > 0.0 {
+                (1, (origin.floor() + 1.0 - origin) / direction, 1.0 / direction)
+            } else if direction < 0.0 {
+                (-1, (origin - origin.floor()) / -direction, -1.0 / direction)
+            } else {
+                (0, f32::INFINITY, f32::INFINITY)
+            }
+        };
+        let (step_x, mut t_max_x, t_delta_x) = axis(origin.x, direction.x);
+        let (step_y, mut t_max_y, t_delta_y) = axis(origin.y, direction.y);
+        let (step_z, mut t_max_z, t_delta_z) = axis(origin.z, direction.z);
+
+        loop {
+            if self.block(pos).is_targetable() {
+                return Some(RaycastHit { pos, face });
+            }
+
+            if t_max_x < t_max_y && t_max_x < t_max_z {
+                if t_max_x > max_distance {
+                    return None;
+                }
+                pos.x += step_x;
+                t_max_x += t_delta_x;
+                face = Some(if step_x > 0 { Direction::West } else { Direction::East });
+            } else if t_max_y < t_max_z {
+                if t_max_y > max_distance {
+                    return None;
+                }
+                pos.y += step_y;
+                t_max_y += t_delta_y;
+                face = Some(if step_y > 0 { Direction::Down } else { Direction::Up });
+            } else {
+                if t_max_z > max_distance {
+                    return None;
+                }
+                pos.z += step_z;
+                t_max_z += t_delta_z;
+                face = Some(if step_z > 0 { Direction::North } else { Direction::South });
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use thiserror::Error;
+
+use crate::world::block::Block;
+use crate::world::chunk::{ChunkData, CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH};
+use crate::world::generation::{GeneratorError, GeneratorKind, GeneratorSettings};
+use crate::world::position::ChunkPos;
+use crate::world::World;
+
+//a saved world is a folder with a `level.txt` holding the seed, the time and the generator
+//settings in the same format as the config, next to a `chunks` folder with one file per chunk named `<x>.<z>.chunk`
+//
+//chunk files start with `CHUNK_MAGIC` and the format version, followed by the zlib compressed
+//id and data byte of every block, going through x first, then z and then y. light isn't saved,
+//it's computed again when the chunk is loaded
+const CHUNK_MAGIC: &[u8; 4] = b"CRCH";
+const VERSION: u8 = 1;
+
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error{"{0}"}]
+    IoError(#[from] io::Error),
+    #[error("`{0}` is not a chunk file of a supported version")]
+    InvalidChunk(PathBuf),
+    #[error("Unknown block id {0}")]
+    UnknownBlock(u8),
+    #[error("Invalid level file: {0}")]
+    InvalidLevel(String),
+    #[error{"{0}"}]
+    GeneratorError(#[from] GeneratorError),
+}
+
+//what's saved about the world besides its chunks
+pub struct Level {
+    pub seed: i64,
+    pub time: u64,
+    pub generator: GeneratorSettings,
+}
+
+fn chunk_path(dir: &Path, pos: ChunkPos) -> PathBuf {
+    dir.join("chunks")
+        .join(format!("{}.{}.chunk", pos.x, pos.z))
+}
+
+pub fn write_chunk(dir: &Path, pos: ChunkPos, chunk: &ChunkData) -> Result<(), SaveError> {
+    let mut file = BufWriter::new(File::create(chunk_path(dir, pos))?);
+    file.write_all(CHUNK_MAGIC)?;
+    file.write_all(&[VERSION])?;
+
+    let mut encoder = ZlibEncoder::new(file, Compression::default());
+    for y in 0..CHUNK_HEIGHT {
+        for z in 0..CHUNK_DEPTH {
+            for x in 0..CHUNK_WIDTH {
+                let block = chunk.block(x, y, z);
+                encoder.write_all(&[block.id(), block.data()])?;
+            }
+        }
+    }
+    encoder.finish()?.flush()?;
+    Ok(())
+}
+
+//the chunk's blocks, without any light
+pub fn read_chunk(dir: &Path, pos: ChunkPos) -> Result<ChunkData, SaveError> {
+    let path = chunk_path(dir, pos);
+    let mut file = BufReader::new(File::open(&path)?);
+    let mut header = [0; 5];
+    file.read_exact(&mut header)?;
+    if &header[..4] != CHUNK_MAGIC || header[4] != VERSION {
+        return Err(SaveError::InvalidChunk(path));
+    }
+
+    let mut blocks = Vec::new();
+    ZlibDecoder::new(file).read_to_end(&mut blocks)?;
+    if blocks.len() != CHUNK_WIDTH * CHUNK_HEIGHT * CHUNK_DEPTH * 2 {
+        return Err(SaveError::InvalidChunk(path));
+    }
+
+    let mut chunk = ChunkData::new();
+    let mut blocks = blocks.chunks_exact(2);
+    for y in 0..CHUNK_HEIGHT {
+        for z in 0..CHUNK_DEPTH {
+            for x in 0..CHUNK_WIDTH {
+                let block = blocks.next().unwrap();
+                let block =
+                    Block::from_id(block[0], block[1]).ok_or(SaveError::UnknownBlock(block[0]))?;
+                chunk.set_block(x, y, z, block);
+            }
+        }
+    }
+    Ok(chunk)
+}
+
+//positions of every chunk in the save, sorted
+pub fn saved_chunks(dir: &Path) -> Result<Vec<ChunkPos>, SaveError> {
+    let mut chunks = Vec::new();
+    for entry in fs::read_dir(dir.join("chunks"))? {
+        let name = entry?.file_name();
+        let pos = name
+            .to_str()
+            .and_then(|name| name.strip_suffix(".chunk"))
+            .and_then(|name| name.split_once('.'))
+            .and_then(|(x, z)| Some(ChunkPos::new(x.parse().ok()?, z.parse().ok()?)));
+        if let Some(pos) = pos {
+            chunks.push(pos);
+        }
+    }
+    chunks.sort();
+    Ok(chunks)
+}
+
+pub fn write_level(dir: &Path, level: &Level) -> Result<(), SaveError> {
+    let generator = &level.generator;
+    fs::write(
+        dir.join("level.txt"),
+        format!(
+            "seed = {}\ntime = {}\ngenerator = {}\ncaves = {}\nsuperflat_layers = {}\nores = {}\n",
+            level.seed,
+            level.time,
+            generator.kind.name(),
+            generator.caves,
+            generator.superflat_layers,
+            generator.ores
+        ),
+    )?;
+    Ok(())
+}
+
+pub fn read_level(dir: &Path) -> Result<Level, SaveError> {
+    let src = fs::read_to_string(dir.join("level.txt"))?;
+    let mut seed = None;
+    let mut time = None;
+    //saves from before the generator settings were written only have the chunks in them
+    let mut generator = GeneratorSettings {
+        kind: GeneratorKind::Void,
+        caves: false,
+        superflat_layers: String::new(),
+        ores: String::new(),
+    };
+    for line in src.lines().filter(|line| !line.trim().is_empty()) {
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| SaveError::InvalidLevel(line.to_string()))?;
+        let (key, value) = (key.trim(), value.trim());
+        let invalid = || SaveError::InvalidLevel(line.to_string());
+        match key {
+            "seed" => seed = Some(value.parse().map_err(|_| invalid())?),
+            "time" => time = Some(value.parse().map_err(|_| invalid())?),
+            "generator" => generator.kind = value.parse().map_err(|_| invalid())?,
+            "caves" => generator.caves = value.parse().map_err(|_| invalid())?,
+            "superflat_layers" => generator.superflat_layers = value.to_string(),
+            "ores" => generator.ores = value.to_string(),
+            _ => return Err(invalid()),
+        }
+    }
+
+    match (seed, time) {
+        (Some(seed), Some(time)) => Ok(Level {
+            seed,
+            time,
+            generator,
+        }),
+        _ => Err(SaveError::InvalidLevel("missing seed or time".to_string())),
+    }
+}
+
+impl World {
+    //the world saved in the folder, chunks which aren't in the save are generated with the saved
+    //generator settings when they're needed
+    pub fn load(dir: &Path) -> Result<World, SaveError> {
+        let level = read_level(dir)?;
+        let mut world = World::new(level.seed, level.generator.build(level.seed)?);
+        world.time_mut().set(level.time);
+        for pos in saved_chunks(dir)? {
+            world.add_saved_chunk(pos, read_chunk(dir, pos)?);
+        }
+        Ok(world)
+    }
+
+    //writes every loaded chunk, decorations still waiting for their chunk to be generated are lost.
+    //`generator` has to be what the world was created with so it can be loaded again
+    pub fn save(&self, dir: &Path, generator: &GeneratorSettings) -> Result<(), SaveError> {
+        fs::create_dir_all(dir.join("chunks"))?;
+        write_level(
+            dir,
+            &Level {
+                seed: self.seed(),
+                time: self.time().ticks(),
+                generator: generator.clone(),
+            },
+        )?;
+
+        for pos in self.loaded_chunks() {
+            write_chunk(dir, pos, self.chunk(pos).unwrap())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::position::BlockPos;
+
+    fn superflat() -> GeneratorSettings {
+        GeneratorSettings {
+            kind: GeneratorKind::Superflat,
+            caves: false,
+            superflat_layers: "1*stone,2*dirt".to_string(),
+            ores: String::new(),
+        }
+    }
+
+    #[test]
+    fn loaded_worlds_keep_generating_like_the_saved_one() {
+        let dir = std::env::temp_dir().join(format!("crafty_save_test_{}", std::process::id()));
+        let settings = superflat();
+        let mut world = World::new(7, settings.build(7).unwrap());
+        world.time_mut().set(1234);
+        world.generate_chunk(ChunkPos::new(0, 0));
+        world.set_block(BlockPos::new(3, 3, 4), Block::Glass);
+        world.save(&dir, &settings).unwrap();
+
+        let level = read_level(&dir).unwrap();
+        assert_eq!((level.seed, level.time), (7, 1234));
+        assert_eq!(level.generator, settings);
+
+        let mut loaded = World::load(&dir).unwrap();
+        assert_eq!(loaded.time().ticks(), 1234);
+        loaded.generate_chunk(ChunkPos::new(0, 0));
+        loaded.generate_chunk(ChunkPos::new(5, 5));
+        assert_eq!(loaded.block(BlockPos::new(3, 3, 4)), Block::Glass);
+        //not in the save, generated with the saved settings
+        assert_eq!(loaded.block(BlockPos::new(83, 2, 84)), Block::Dirt);
+        assert_eq!(loaded.block(BlockPos::new(83, 3, 84)), Block::Air);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn old_levels_without_generator_settings_use_the_void_generator() {
+        let dir = std::env::temp_dir().join(format!("crafty_level_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("level.txt"), "seed = 3\ntime = 10\n").unwrap();
+        let level = read_level(&dir).unwrap();
+        assert_eq!(level.generator.kind, GeneratorKind::Void);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
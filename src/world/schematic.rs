@@ -0,0 +1,488 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use thiserror::Error;
+
+use crate::utils::nbt::{self, NbtError, Tag};
+use crate::world::block::Block;
+use crate::world::edit::{Axis, Rotation};
+use crate::world::fluid::SOURCE;
+use crate::world::position::BlockPos;
+use crate::world::World;
+
+//sponge schematics, the format worldedit and most other tools use to share builds. they're
+//gzipped nbt with a palette of block states like `minecraft:water[level=0]` and the index of
+//every block's state in the palette as varints, going through x first, then z and then y
+//
+//version 2 is written since it's what most tools read, versions 1 to 3 can be read
+const VERSION: i32 = 2;
+//the minecraft version the block names come from, 1.20.1
+const DATA_VERSION: i32 = 3465;
+
+#[derive(Debug, Error)]
+pub enum SchematicError {
+    #[error{"{0}"}]
+    IoError(#[from] io::Error),
+    #[error{"{0}"}]
+    NbtError(#[from] NbtError),
+    #[error("Unsupported schematic version {0}")]
+    UnsupportedVersion(i64),
+    #[error("Missing or invalid `{0}` tag")]
+    InvalidTag(&'static str),
+    #[error("The block data doesn't match the size of the schematic")]
+    InvalidBlockData,
+    #[error("Schematics can be at most 65535 blocks along each side")]
+    TooBig,
+}
+
+pub struct Schematic {
+    width: usize,
+    height: usize,
+    length: usize,
+    blocks: Vec<Block>,
+}
+
+//the name a block has in minecraft, blocks with data keep it as a property
+fn external_name(block: Block) -> String {
+    match block {
+        Block::Grass => "minecraft:grass_block".to_string(),
+        Block::Leaves => "minecraft:oak_leaves".to_string(),
+        Block::StainedGlass => "minecraft:red_stained_glass".to_string(),
+        Block::Log => "minecraft:oak_log".to_string(),
+        Block::Flower => "minecraft:poppy".to_string(),
+        //fluid levels and crop ages count the same way as ours
+        Block::Water(level) => format!("minecraft:water[level={}]", level),
+        Block::Lava(level) => format!("minecraft:lava[level={}]", level),
+        Block::Wheat(age) => format!("minecraft:wheat[age={}]", age),
+        _ => format!("minecraft:{}", block.name()),
+    }
+}
+
+//the closest block we have to a block state from minecraft, variants we don't have like the other
+//kinds of wood all become the one we do have
+fn from_external_name(state: &str) -> Option<Block> {
+    let (name, properties) = match state.split_once('[') {
+        Some((name, properties)) => (name, properties.trim_end_matches(']')),
+        None => (state, ""),
+    };
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    let property = |key: &str| {
+        properties
+            .split(',')
+            .filter_map(|property| property.split_once('='))
+            .find(|(property, _)| property.trim() == key)
+            .and_then(|(_, value)| value.trim().parse::<u8>().ok())
+    };
+
+    let block = match name {
+        "air" | "cave_air" | "void_air" => Block::Air,
+        "grass_block" => Block::Grass,
+        "wall_torch" => Block::Torch,
+        "water" => Block::Water(property("level").unwrap_or(SOURCE).min(8)),
+        "lava" => Block::Lava(property("level").unwrap_or(SOURCE).min(8)),
+        "wheat" => Block::Wheat(property("age").unwrap_or(0).min(7)),
+        "dandelion" | "poppy" | "blue_orchid" | "allium" | "azure_bluet" | "red_tulip"
+        | "orange_tulip" | "white_tulip" | "pink_tulip" | "oxeye_daisy" | "cornflower"
+        | "lily_of_the_valley" => Block::Flower,
+        //the plant, not the block
+        "grass" => return None,
+        _ if name.ends_with("_leaves") => Block::Leaves,
+        _ if name.ends_with("_log") || name.ends_with("_wood") => Block::Log,
+        _ if name.ends_with("_stained_glass") => Block::StainedGlass,
+        _ => Block::from_name(name.strip_prefix("deepslate_").unwrap_or(name))?,
+    };
+    Some(block)
+}
+
+fn write_varint(bytes: &mut Vec<i8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte as i8);
+            return;
+        }
+        bytes.push((byte | 0x80) as i8);
+    }
+}
+
+fn read_varints(bytes: &[i8]) -> Result<Vec<u32>, SchematicError> {
+    let mut values = Vec::new();
+    let mut value = 0;
+    let mut shift = 0;
+    for byte in bytes.iter().map(|byte| *byte as u8) {
+        if shift > 28 {
+            return Err(SchematicError::InvalidBlockData);
+        }
+        value |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            values.push(value);
+            value = 0;
+            shift = 0;
+        } else {
+            shift += 7;
+        }
+    }
+    if shift != 0 {
+        return Err(SchematicError::InvalidBlockData);
+    }
+    Ok(values)
+}
+
+impl Schematic {
+    //the blocks between the two corners, both included
+    pub fn copy(world: &World, from: BlockPos, to: BlockPos) -> Self {
+        let min = BlockPos::new(from.x.min(to.x), from.y.min(to.y), from.z.min(to.z));
+        let max = BlockPos::new(from.x.max(to.x), from.y.max(to.y), from.z.max(to.z));
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let length = (max.z - min.z + 1) as usize;
+
+        let mut blocks = Vec::with_capacity(width * height * length);
+        for y in min.y..=max.y {
+            for z in min.z..=max.z {
+                for x in min.x..=max.x {
+                    blocks.push(world.block(BlockPos::new(x, y, z)));
+                }
+            }
+        }
+        Self {
+            width,
+            height,
+            length,
+            blocks,
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize, usize) {
+        (self.width, self.height, self.length)
+    }
+
+    //the block at the position relative to the schematic's lowest corner
+    pub fn block(&self, x: usize, y: usize, z: usize) -> Block {
+        self.blocks[(y * self.length + z) * self.width + x]
+    }
+
+    //every block with where it ends up when the lowest corner is at `origin`
+    pub fn blocks_at(&self, origin: BlockPos) -> impl Iterator<Item = (BlockPos, Block)> + '_ {
+        (0..self.height).flat_map(move |y| {
+            (0..self.length).flat_map(move |z| {
+                (0..self.width).map(move |x| {
+                    let pos = origin.offset(x as i32, y as i32, z as i32);
+                    (pos, self.block(x, y, z))
+                })
+            })
+        })
+    }
+
+    //places the schematic with its lowest corner at `origin`, air included, returning how many
+    //blocks were in a loaded part of the world
+    pub fn paste(&self, world: &mut World, origin: BlockPos) -> usize {
+        let placed = self
+            .blocks_at(origin)
+            .filter(|(pos, _)| world.contains(*pos))
+            .count();
+        world.set_blocks(self.blocks_at(origin));
+        placed
+    }
+
+    //a schematic of the given size where `block` decides what goes where
+    fn remapped(
+        &self,
+        (width, height, length): (usize, usize, usize),
+        block: impl Fn(usize, usize, usize) -> Block,
+    ) -> Self {
+        let mut blocks = Vec::with_capacity(self.blocks.len());
+        for y in 0..height {
+            for z in 0..length {
+                for x in 0..width {
+                    blocks.push(block(x, y, z));
+                }
+            }
+        }
+        Self {
+            width,
+            height,
+            length,
+            blocks,
+        }
+    }
+
+    pub fn rotated(&self, rotation: Rotation) -> Self {
+        let (width, height, length) = (self.width, self.height, self.length);
+        match rotation {
+            //x becomes z and z becomes -x, so the box swaps its width and length
+            Rotation::Clockwise => self.remapped((length, height, width), |x, y, z| {
+                self.block(z, y, length - 1 - x)
+            }),
+            Rotation::Half => self.remapped((width, height, length), |x, y, z| {
+                self.block(width - 1 - x, y, length - 1 - z)
+            }),
+            Rotation::CounterClockwise => self.remapped((length, height, width), |x, y, z| {
+                self.block(width - 1 - z, y, x)
+            }),
+        }
+    }
+
+    //flipped along the axis
+    pub fn mirrored(&self, axis: Axis) -> Self {
+        let (width, height, length) = (self.width, self.height, self.length);
+        self.remapped((width, height, length), |x, y, z| match axis {
+            Axis::X => self.block(width - 1 - x, y, z),
+            Axis::Y => self.block(x, height - 1 - y, z),
+            Axis::Z => self.block(x, y, length - 1 - z),
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), SchematicError> {
+        let size = |value: usize| {
+            u16::try_from(value)
+                .map(|value| Tag::Short(value as i16))
+                .map_err(|_| SchematicError::TooBig)
+        };
+
+        let mut palette: HashMap<Block, i32> = HashMap::new();
+        let mut data = Vec::with_capacity(self.blocks.len());
+        for block in self.blocks.iter() {
+            let next = palette.len() as i32;
+            let index = *palette.entry(*block).or_insert(next);
+            write_varint(&mut data, index as u32);
+        }
+
+        let mut root = BTreeMap::new();
+        root.insert("Version".to_string(), Tag::Int(VERSION));
+        root.insert("DataVersion".to_string(), Tag::Int(DATA_VERSION));
+        root.insert("Width".to_string(), size(self.width)?);
+        root.insert("Height".to_string(), size(self.height)?);
+        root.insert("Length".to_string(), size(self.length)?);
+        root.insert("Offset".to_string(), Tag::IntArray(vec![0, 0, 0]));
+        root.insert("PaletteMax".to_string(), Tag::Int(palette.len() as i32));
+        root.insert(
+            "Palette".to_string(),
+            Tag::Compound(
+                palette
+                    .into_iter()
+                    .map(|(block, index)| (external_name(block), Tag::Int(index)))
+                    .collect(),
+            ),
+        );
+        root.insert("BlockData".to_string(), Tag::ByteArray(data));
+        root.insert("BlockEntities".to_string(), Tag::List(Vec::new()));
+
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        nbt::write(&mut encoder, "Schematic", &Tag::Compound(root))?;
+        encoder.finish()?.flush()?;
+        Ok(())
+    }
+
+    //the schematic and the names of the blocks in it we don't have, which become air
+    pub fn read(path: &Path) -> Result<(Self, Vec<String>), SchematicError> {
+        let file = BufReader::new(File::open(path)?);
+        let (_, root) = nbt::read(&mut GzDecoder::new(file))?;
+        //version 3 puts everything in a `Schematic` compound inside of the unnamed root
+        let root = match root.get("Schematic") {
+            Some(schematic @ Tag::Compound(_)) => schematic,
+            _ => &root,
+        };
+
+        let version = root
+            .get("Version")
+            .and_then(Tag::as_i64)
+            .ok_or(SchematicError::InvalidTag("Version"))?;
+        let blocks = match version {
+            1 | 2 => root,
+            3 => root
+                .get("Blocks")
+                .ok_or(SchematicError::InvalidTag("Blocks"))?,
+            _ => return Err(SchematicError::UnsupportedVersion(version)),
+        };
+        let data_name = if version == 3 { "Data" } else { "BlockData" };
+
+        //sizes are unsigned shorts stored in signed ones
+        let size = |name: &'static str| {
+            root.get(name)
+                .and_then(Tag::as_i64)
+                .map(|value| (value & 0xffff) as usize)
+                .ok_or(SchematicError::InvalidTag(name))
+        };
+        let (width, height, length) = (size("Width")?, size("Height")?, size("Length")?);
+
+        let mut unknown = Vec::new();
+        let mut palette = HashMap::new();
+        match blocks.get("Palette") {
+            Some(Tag::Compound(states)) => {
+                for (state, index) in states {
+                    let index = index
+                        .as_i64()
+                        .ok_or(SchematicError::InvalidTag("Palette"))?;
+                    let block = from_external_name(state).unwrap_or_else(|| {
+                        unknown.push(state.clone());
+                        Block::Air
+                    });
+                    palette.insert(index as u32, block);
+                }
+            }
+            _ => return Err(SchematicError::InvalidTag("Palette")),
+        }
+
+        let data = match blocks.get(data_name) {
+            Some(Tag::ByteArray(data)) => read_varints(data)?,
+            _ => return Err(SchematicError::InvalidTag(data_name)),
+        };
+        if data.len() != width * height * length {
+            return Err(SchematicError::InvalidBlockData);
+        }
+        let blocks = data
+            .into_iter()
+            .map(|index| {
+                palette
+                    .get(&index)
+                    .copied()
+                    .ok_or(SchematicError::InvalidBlockData)
+            })
+            .collect::<Result<Vec<Block>, SchematicError>>()?;
+
+        unknown.sort();
+        Ok((
+            Self {
+                width,
+                height,
+                length,
+                blocks,
+            },
+            unknown,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::world::chunk::ChunkData;
+    use crate::world::generation::VoidGenerator;
+    use crate::world::position::ChunkPos;
+
+    #[test]
+    fn reads_version_2_schematics() {
+        let (schematic, unknown) =
+            Schematic::read(Path::new("src/resources/test/sponge_v2.schem")).unwrap();
+        assert_eq!(schematic.size(), (3, 2, 2));
+        assert_eq!(
+            unknown,
+            vec!["minecraft:chest[facing=north]", "minecraft:grass"]
+        );
+
+        assert_eq!(schematic.block(0, 0, 0), Block::Stone);
+        assert_eq!(schematic.block(0, 0, 1), Block::Log);
+        //its palette index takes two bytes
+        assert_eq!(schematic.block(1, 0, 1), Block::Water(SOURCE));
+        assert_eq!(schematic.block(2, 0, 1), Block::Stone);
+        assert_eq!(schematic.block(0, 1, 0), Block::Air);
+        //unknown blocks become air
+        assert_eq!(schematic.block(1, 1, 0), Block::Air);
+        assert_eq!(schematic.block(2, 1, 0), Block::Air);
+    }
+
+    #[test]
+    fn reads_version_3_schematics() {
+        let (schematic, unknown) =
+            Schematic::read(Path::new("src/resources/test/sponge_v3.schem")).unwrap();
+        assert_eq!(schematic.size(), (2, 1, 2));
+        assert_eq!(unknown, vec!["minecraft:furnace[facing=east,lit=false]"]);
+
+        assert_eq!(schematic.block(0, 0, 0), Block::IronOre);
+        assert_eq!(schematic.block(1, 0, 0), Block::StainedGlass);
+        assert_eq!(schematic.block(0, 0, 1), Block::Lava(SOURCE));
+        assert_eq!(schematic.block(1, 0, 1), Block::Air);
+    }
+
+    #[test]
+    fn blocks_keep_their_data_through_a_write_and_read() {
+        let blocks = [
+            Block::Stone,
+            Block::Water(SOURCE),
+            Block::Water(3),
+            Block::Lava(7),
+            Block::Wheat(0),
+            Block::Wheat(7),
+            Block::Grass,
+            Block::Leaves,
+            Block::StainedGlass,
+            Block::Log,
+            Block::Flower,
+            Block::CraftingTable,
+        ];
+        //placed straight into the chunk so nothing flows, grows or breaks
+        let mut chunk = ChunkData::new();
+        for (i, block) in blocks.iter().enumerate() {
+            chunk.set_block(i % 4, 5 + i / 8, i / 4 % 2, *block);
+        }
+        let mut world = World::new(1, Box::new(VoidGenerator));
+        world.insert_chunk(ChunkPos::new(0, 0), chunk);
+
+        let copied = Schematic::copy(&world, BlockPos::new(3, 6, 1), BlockPos::new(0, 5, 0));
+        assert_eq!(copied.size(), (4, 2, 2));
+        let path = std::env::temp_dir().join(format!("crafty_{}.schem", std::process::id()));
+        copied.write(&path).unwrap();
+        let (read, unknown) = Schematic::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(unknown.is_empty());
+        assert_eq!(read.size(), copied.size());
+        assert_eq!(read.blocks, copied.blocks);
+        for (i, block) in blocks.iter().enumerate() {
+            assert_eq!(read.block(i % 4, i / 8, i / 4 % 2), *block);
+        }
+    }
+
+    #[test]
+    fn blocks_use_minecraft_names() {
+        assert_eq!(external_name(Block::Stone), "minecraft:stone");
+        assert_eq!(external_name(Block::Grass), "minecraft:grass_block");
+        assert_eq!(external_name(Block::Leaves), "minecraft:oak_leaves");
+        assert_eq!(external_name(Block::Log), "minecraft:oak_log");
+        assert_eq!(external_name(Block::Flower), "minecraft:poppy");
+        assert_eq!(
+            external_name(Block::StainedGlass),
+            "minecraft:red_stained_glass"
+        );
+        assert_eq!(external_name(Block::Water(2)), "minecraft:water[level=2]");
+        assert_eq!(
+            external_name(Block::Lava(SOURCE)),
+            "minecraft:lava[level=0]"
+        );
+        assert_eq!(external_name(Block::Wheat(5)), "minecraft:wheat[age=5]");
+    }
+
+    #[test]
+    fn varints_take_as_many_bytes_as_they_need() {
+        let values = [0, 1, 127, 128, 300, 16_383, 16_384, u32::MAX];
+        let mut bytes = Vec::new();
+        for value in values {
+            write_varint(&mut bytes, value);
+        }
+        assert_eq!(read_varints(&bytes).unwrap(), values);
+        assert_eq!(read_varints(&[0xac_u8 as i8, 0x02]).unwrap(), vec![300]);
+    }
+
+    #[test]
+    fn truncated_varints_are_invalid() {
+        assert!(matches!(
+            read_varints(&[1, 0x80_u8 as i8]),
+            Err(SchematicError::InvalidBlockData)
+        ));
+        //more bytes than any u32 needs
+        assert!(matches!(
+            read_varints(&[0xff_u8 as i8; 6]),
+            Err(SchematicError::InvalidBlockData)
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::world::position::BlockPos;
+
+//queue of block updates which should happen a given number of ticks in the future, updates due
+//on the same tick run in the order they were scheduled so the simulation is deterministic
+pub struct TickScheduler {
+    current_tick: u64,
+    //used to keep the scheduling order between updates due on the same tick
+    next_id: u64,
+    queue: BinaryHeap<Reverse<(u64, u64, BlockPos)>>,
+    //a position is only ever queued once, later requests are dropped until it has been updated
+    pending: HashSet<BlockPos>,
+}
+
+impl TickScheduler {
+    pub fn new() -> Self {
+        Self {
+            current_tick: 0,
+            next_id: 0,
+            queue: BinaryHeap::new(),
+            pending: HashSet::new(),
+        }
+    }
+
+    pub fn schedule(&mut self, pos: BlockPos, delay: u64) {
+        if !self.pending.insert(pos) {
+            return;
+        }
+        self.queue
+            .push(Reverse((self.current_tick + delay.max(1), self.next_id, pos)));
+        self.next_id += 1;
+    }
+
+    //moves on to the next tick and returns every position due on it
+    pub fn advance(&mut self) -> Vec<BlockPos> {
+        self.current_tick += 1;
+
+        let mut due = Vec::new();
+        while let Some(Reverse((tick, _, pos))) = self.queue.peek().copied() {
+            if tick > self.current_tick {
+                break;
+            }
+            self.queue.pop();
+            self.pending.remove(&pos);
+            due.push(pos);
+        }
+        due
+    }
+}
+
+impl Default for TickScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
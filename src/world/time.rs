@@ -0,0 +1,82 @@
+use std::f32::consts::PI;
+
+use crate::utils::math;
+
+//ticks in a full day, 0 is sunrise, a quarter is noon, a half is sunset and three quarters is midnight
+pub const DAY_LENGTH: u64 = 24000;
+
+const DAY_SKY: [f32; 3] = [0.47, 0.68, 1.0];
+const NIGHT_SKY: [f32; 3] = [0.01, 0.01, 0.05];
+const SUNSET_SKY: [f32; 3] = [0.95, 0.45, 0.2];
+//how much darker sky light gets at midnight
+const NIGHT_BRIGHTNESS: f32 = 0.2;
+
+pub struct WorldTime {
+    ticks: u64,
+    frozen: bool,
+}
+
+fn mix(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+    ]
+}
+
+impl WorldTime {
+    pub fn new(ticks: u64, frozen: bool) -> Self {
+        Self { ticks, frozen }
+    }
+
+    pub fn tick(&mut self) {
+        if !self.frozen {
+            self.ticks += 1;
+        }
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn set(&mut self, ticks: u64) {
+        self.ticks = ticks;
+    }
+
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
+    //from 0.0 to 1.0
+    pub fn time_of_day(&self) -> f32 {
+        (self.ticks % DAY_LENGTH) as f32 / DAY_LENGTH as f32
+    }
+
+    //sine of the sun's angle above the horizon, 1.0 at noon and -1.0 at midnight
+    fn sun_height(&self) -> f32 {
+        (self.time_of_day() * 2.0 * PI).sin()
+    }
+
+    //direction the sunlight travels in, the sun rises in the east and sets in the west
+    pub fn sun_direction(&self) -> math::Vec3 {
+        let angle = self.time_of_day() * 2.0 * PI;
+        //slightly tilted so that the sun never lines up exactly with the block faces
+        math::Vec3::new(-angle.cos(), -angle.sin(), -0.3).normalize()
+    }
+
+    //0.0 for night and 1.0 for day, with a smooth transition while the sun is close to the horizon
+    fn daylight(&self) -> f32 {
+        ((self.sun_height() + 0.2) / 0.4).clamp(0.0, 1.0)
+    }
+
+    pub fn sky_brightness(&self) -> f32 {
+        NIGHT_BRIGHTNESS + (1.0 - NIGHT_BRIGHTNESS) * self.daylight()
+    }
+
+    pub fn sky_colour(&self) -> [f32; 3] {
+        let colour = mix(NIGHT_SKY, DAY_SKY, self.daylight());
+        //reddish tint around sunrise and sunset
+        let sunset = (1.0 - self.sun_height().abs() / 0.25).clamp(0.0, 1.0);
+        mix(colour, SUNSET_SKY, sunset * 0.6)
+    }
+}
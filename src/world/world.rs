@@ -0,0 +1,385 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::utils::random::Random;
+use crate::world::biome::Biome;
+use crate::world::block::Block;
+use crate::world::chunk::{
+    ChunkData, CHUNK_DEPTH, CHUNK_HEIGHT, CHUNK_WIDTH, MAX_LIGHT, SECTION_HEIGHT,
+};
+use crate::world::decoration;
+use crate::world::entity::{self, FallingBlock};
+use crate::world::generation::WorldGenerator;
+use crate::world::light;
+use crate::world::position::{BlockPos, ChunkPos, Direction};
+use crate::world::tick::TickScheduler;
+use crate::world::time::WorldTime;
+
+//blocks picked for a random tick in every chunk section, every tick
+const RANDOM_TICKS_PER_SECTION: u32 = 3;
+
+pub struct World {
+    seed: i64,
+    generator: Box<dyn WorldGenerator>,
+    time: WorldTime,
+    scheduler: TickScheduler,
+    random: Random,
+    falling_blocks: Vec<FallingBlock>,
+    chunks: HashMap<ChunkPos, ChunkData>,
+    //blocks of decorations reaching into chunks which aren't generated yet, placed once they are
+    pending_decorations: HashMap<ChunkPos, Vec<(BlockPos, Block)>>,
+    //chunks read from a save, used instead of generating them once they're needed
+    saved_chunks: HashMap<ChunkPos, ChunkData>,
+    //chunks whose blocks or light changed since the renderer last rebuilt their meshes
+    dirty_chunks: HashSet<ChunkPos>,
+}
+
+impl World {
+    pub fn new(seed: i64, generator: Box<dyn WorldGenerator>) -> Self {
+        Self {
+            seed,
+            generator,
+            time: WorldTime::new(0, false),
+            scheduler: TickScheduler::new(),
+            random: Random::new(seed),
+            falling_blocks: Vec::new(),
+            chunks: HashMap::new(),
+            pending_decorations: HashMap::new(),
+            saved_chunks: HashMap::new(),
+            dirty_chunks: HashSet::new(),
+        }
+    }
+
+    pub fn seed(&self) -> i64 {
+        self.seed
+    }
+
+    pub fn time(&self) -> &WorldTime {
+        &self.time
+    }
+
+    pub fn time_mut(&mut self) -> &mut WorldTime {
+        &mut self.time
+    }
+
+    pub fn tick(&mut self) {
+        self.time.tick();
+
+        for pos in self.scheduler.advance() {
+            let block = self.block(pos);
+            block.on_scheduled_tick(self, pos);
+        }
+
+        self.random_ticks();
+        entity::update_falling_blocks(self);
+    }
+
+    pub fn falling_blocks(&self) -> &[FallingBlock] {
+        &self.falling_blocks
+    }
+
+    pub(super) fn falling_blocks_mut(&mut self) -> &mut Vec<FallingBlock> {
+        &mut self.falling_blocks
+    }
+
+    fn random_ticks(&mut self) {
+        //taken out of self so it can be handed to the blocks together with the world
+        let mut random = self.random.clone();
+
+        //sorted so the same seed always produces the same updates
+        let mut chunks: Vec<ChunkPos> = self.chunks.keys().copied().collect();
+        chunks.sort();
+
+        for chunk_pos in chunks {
+            for section in 0..CHUNK_HEIGHT.div_ceil(SECTION_HEIGHT) {
+                for _ in 0..RANDOM_TICKS_PER_SECTION {
+                    let x = random.next_below(CHUNK_WIDTH as u32) as usize;
+                    let y = section * SECTION_HEIGHT
+                        + random.next_below(SECTION_HEIGHT as u32) as usize;
+                    let z = random.next_below(CHUNK_DEPTH as u32) as usize;
+                    if y >= CHUNK_HEIGHT {
+                        continue;
+                    }
+
+                    let pos = chunk_pos.block_pos(x, y, z);
+                    let block = self.block(pos);
+                    if block.has_random_ticks() {
+                        block.on_random_tick(self, pos, &mut random);
+                    }
+                }
+            }
+        }
+
+        self.random = random;
+    }
+
+    //asks for `pos` to be updated after `delay` ticks
+    pub fn schedule_tick(&mut self, pos: BlockPos, delay: u64) {
+        self.scheduler.schedule(pos, delay);
+    }
+
+    pub fn is_loaded(&self, pos: ChunkPos) -> bool {
+        self.chunks.contains_key(&pos)
+    }
+
+    pub fn chunk(&self, pos: ChunkPos) -> Option<&ChunkData> {
+        self.chunks.get(&pos)
+    }
+
+    //in no particular order
+    pub fn loaded_chunks(&self) -> impl Iterator<Item = ChunkPos> + '_ {
+        self.chunks.keys().copied()
+    }
+
+    //whether the position is inside of the world's height and in a loaded chunk
+    pub fn contains(&self, pos: BlockPos) -> bool {
+        pos.y >= 0 && pos.y < CHUNK_HEIGHT as i32 && self.is_loaded(pos.chunk_pos())
+    }
+
+    pub fn generate_chunk(&mut self, pos: ChunkPos) {
+        if self.is_loaded(pos) {
+            return;
+        }
+
+        let (mut chunk, decorations) = match self.saved_chunks.remove(&pos) {
+            //saved chunks already have their own decorations
+            Some(chunk) => (chunk, Vec::new()),
+            None => {
+                let chunk = self.generator.generate(pos);
+                //decided before anything from the neighbours is placed so that it only depends on
+                //the terrain
+                let decorations = self.generator.decorate(pos, &chunk);
+                (chunk, decorations)
+            }
+        };
+        let pending = self.pending_decorations.remove(&pos).unwrap_or_default();
+        for (block_pos, block) in pending {
+            place_decoration(&mut chunk, block_pos, block);
+        }
+        let mut outside = Vec::new();
+        for (block_pos, block) in decorations {
+            if block_pos.chunk_pos() == pos {
+                place_decoration(&mut chunk, block_pos, block);
+            } else {
+                outside.push((block_pos, block));
+            }
+        }
+
+        self.insert_chunk(pos, chunk);
+
+        //neighbours which are already there have their light and meshes updated like for any
+        //other change
+        for (block_pos, block) in outside {
+            let chunk_pos = block_pos.chunk_pos();
+            if self.is_loaded(chunk_pos) {
+                if decoration::can_replace(self.block(block_pos), block) {
+                    self.set_block(block_pos, block);
+                }
+            } else {
+                self.pending_decorations
+                    .entry(chunk_pos)
+                    .or_default()
+                    .push((block_pos, block));
+            }
+        }
+    }
+
+    //the chunk is loaded instead of generated the next time `generate_chunk` is called for it
+    pub(super) fn add_saved_chunk(&mut self, pos: ChunkPos, chunk: ChunkData) {
+        self.saved_chunks.insert(pos, chunk);
+    }
+
+    //adds a chunk which already has its blocks, like one read from a save, and lights it
+    pub fn insert_chunk(&mut self, pos: ChunkPos, chunk: ChunkData) {
+        self.chunks.insert(pos, chunk);
+        light::light_chunk(self, pos);
+
+        //neighbours may have had faces along the shared border which are now hidden
+        self.dirty_chunks.insert(pos);
+        for direction in Direction::HORIZONTAL {
+            let (dx, _, dz) = direction.offset();
+            let neighbour = ChunkPos::new(pos.x + dx, pos.z + dz);
+            if self.is_loaded(neighbour) {
+                self.dirty_chunks.insert(neighbour);
+            }
+        }
+    }
+
+    //biomes only depend on the column, so this works for chunks which aren't generated yet too
+    pub fn biome(&self, pos: BlockPos) -> Biome {
+        self.generator.biome_at(pos.x, pos.z)
+    }
+
+    pub fn block(&self, pos: BlockPos) -> Block {
+        if pos.y < 0 || pos.y >= CHUNK_HEIGHT as i32 {
+            return Block::Air;
+        }
+        match self.chunks.get(&pos.chunk_pos()) {
+            Some(chunk) => {
+                let (x, y, z) = pos.local();
+                chunk.block(x, y, z)
+            }
+            None => Block::Air,
+        }
+    }
+
+    //returns false if the position isn't in a loaded part of the world
+    pub fn set_block(&mut self, pos: BlockPos, block: Block) -> bool {
+        if !self.contains(pos) {
+            return false;
+        }
+
+        let (x, y, z) = pos.local();
+        let chunk = self.chunks.get_mut(&pos.chunk_pos()).unwrap();
+        let old = chunk.block(x, y, z);
+        if old == block {
+            return true;
+        }
+        chunk.set_block(x, y, z, block);
+
+        light::block_changed(self, pos, block);
+        self.mark_dirty(pos);
+        self.notify_neighbours(pos);
+        true
+    }
+
+    //replaces a lot of blocks at once, like for world edits. the light is only computed again once
+    //everything is placed instead of after every block. returns what was there before for every
+    //position that changed, positions outside of the loaded world are skipped
+    pub fn set_blocks(
+        &mut self,
+        blocks: impl IntoIterator<Item = (BlockPos, Block)>,
+    ) -> Vec<(BlockPos, Block)> {
+        let mut changed = Vec::new();
+        let mut chunks = HashSet::new();
+        for (pos, block) in blocks {
+            if !self.contains(pos) {
+                continue;
+            }
+            let (x, y, z) = pos.local();
+            let chunk = self.chunks.get_mut(&pos.chunk_pos()).unwrap();
+            let old = chunk.block(x, y, z);
+            if old == block {
+                continue;
+            }
+            chunk.set_block(x, y, z, block);
+            changed.push((pos, old));
+            chunks.insert(pos.chunk_pos());
+            self.mark_dirty(pos);
+        }
+
+        //light travels less than a chunk, so only the neighbouring chunks can have light coming
+        //from the changed ones
+        let mut relit: Vec<ChunkPos> = chunks
+            .iter()
+            .flat_map(|pos| {
+                Direction::HORIZONTAL.iter().map(move |direction| {
+                    let (dx, _, dz) = direction.offset();
+                    ChunkPos::new(pos.x + dx, pos.z + dz)
+                })
+            })
+            .chain(chunks.iter().copied())
+            .filter(|pos| self.is_loaded(*pos))
+            .collect();
+        relit.sort();
+        relit.dedup();
+        for pos in relit.iter() {
+            self.chunks.get_mut(pos).unwrap().clear_light();
+        }
+        for pos in relit {
+            light::light_chunk(self, pos);
+        }
+
+        for (pos, _) in changed.iter() {
+            self.notify_neighbours(*pos);
+        }
+        changed
+    }
+
+    //lets the changed block and everything around it react to the change
+    fn notify_neighbours(&mut self, pos: BlockPos) {
+        let mut positions = vec![pos];
+        positions.extend(Direction::ALL.iter().map(|direction| pos.neighbour(*direction)));
+
+        for pos in positions {
+            let block = self.block(pos);
+            block.on_neighbour_update(self, pos);
+        }
+    }
+
+    pub fn sky_light(&self, pos: BlockPos) -> u8 {
+        if pos.y >= CHUNK_HEIGHT as i32 {
+            return MAX_LIGHT;
+        }
+        if pos.y < 0 {
+            return 0;
+        }
+        match self.chunks.get(&pos.chunk_pos()) {
+            Some(chunk) => {
+                let (x, y, z) = pos.local();
+                chunk.sky_light(x, y, z)
+            }
+            None => MAX_LIGHT,
+        }
+    }
+
+    pub fn block_light(&self, pos: BlockPos) -> u8 {
+        if !self.contains(pos) {
+            return 0;
+        }
+        let (x, y, z) = pos.local();
+        self.chunks[&pos.chunk_pos()].block_light(x, y, z)
+    }
+
+    pub(super) fn set_sky_light(&mut self, pos: BlockPos, level: u8) {
+        if let Some(chunk) = self.chunks.get_mut(&pos.chunk_pos()) {
+            let (x, y, z) = pos.local();
+            chunk.set_sky_light(x, y, z, level);
+            self.mark_dirty(pos);
+        }
+    }
+
+    pub(super) fn set_block_light(&mut self, pos: BlockPos, level: u8) {
+        if let Some(chunk) = self.chunks.get_mut(&pos.chunk_pos()) {
+            let (x, y, z) = pos.local();
+            chunk.set_block_light(x, y, z, level);
+            self.mark_dirty(pos);
+        }
+    }
+
+    //blocks on the edge of a chunk are also part of the neighbouring chunk's mesh
+    fn mark_dirty(&mut self, pos: BlockPos) {
+        let chunk_pos = pos.chunk_pos();
+        self.dirty_chunks.insert(chunk_pos);
+
+        let (x, _, z) = pos.local();
+        if x == 0 {
+            self.dirty_chunks.insert(ChunkPos::new(chunk_pos.x - 1, chunk_pos.z));
+        }
+        if x == CHUNK_WIDTH - 1 {
+            self.dirty_chunks.insert(ChunkPos::new(chunk_pos.x + 1, chunk_pos.z));
+        }
+        if z == 0 {
+            self.dirty_chunks.insert(ChunkPos::new(chunk_pos.x, chunk_pos.z - 1));
+        }
+        if z == CHUNK_DEPTH - 1 {
+            self.dirty_chunks.insert(ChunkPos::new(chunk_pos.x, chunk_pos.z + 1));
+        }
+    }
+
+    pub fn take_dirty_chunks(&mut self) -> Vec<ChunkPos> {
+        self.dirty_chunks
+            .drain()
+            .filter(|pos| self.chunks.contains_key(pos))
+            .collect()
+    }
+}
+
+fn place_decoration(chunk: &mut ChunkData, pos: BlockPos, block: Block) {
+    if pos.y < 0 || pos.y >= CHUNK_HEIGHT as i32 {
+        return;
+    }
+    let (x, y, z) = pos.local();
+    if decoration::can_replace(chunk.block(x, y, z), block) {
+        chunk.set_block(x, y, z, block);
+    }
+}